@@ -1,18 +1,19 @@
 use {
-    anyhow::Result,
     async_trait::async_trait,
     resvolt::{
+        error::Result,
         models::{events::ReadyEvent, Presence, UserStatus},
-        Client, Context, RevoltCommand, RevoltEventHandler,
-    }, std::env,
+        Args, Client, Context, RevoltCommand, RevoltEventHandler,
+    },
+    std::env,
 };
 
 #[derive(Clone, Default)]
 struct Counter(u32);
 
 impl Counter {
-    fn increment(&mut self) {
-        self.0 += 1;
+    fn add(&mut self, amount: u32) {
+        self.0 += amount;
     }
 }
 
@@ -20,8 +21,13 @@ struct CountCommand;
 
 #[async_trait]
 impl RevoltCommand for CountCommand {
-    async fn execute(&self, ctx: &Context) -> Result<()> {
-        ctx.state.update(Counter::increment).await;
+    async fn execute(&self, ctx: &Context, mut args: Args) -> Result<()> {
+        let amount = args.parse_optional(ctx).await?.unwrap_or(1);
+        args.finish()?;
+
+        ctx.state
+            .update(|counter: &mut Counter| counter.add(amount))
+            .await;
 
         let Counter(current_count) = ctx.state.get().await.unwrap();
         let content = format!("#### Counted!\nCurrent count is **{current_count}**");
@@ -30,6 +36,10 @@ impl RevoltCommand for CountCommand {
 
         Ok(())
     }
+
+    fn usage(&self) -> Option<&str> {
+        Some("[amount]")
+    }
 }
 
 struct EventHandler;
@@ -0,0 +1,22 @@
+use {
+    resvolt::{builders::CreateMessage, error::Result, http::HttpClient, models::Message, Context},
+    std::env,
+};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let token = env::var("TOKEN").expect("No TOKEN environment variable found!");
+    let channel_id = env::var("CHANNEL_ID").expect("No CHANNEL_ID environment variable found!");
+
+    // Only the REST API is used, no WebSocket connection is opened.
+    let ctx = Context::from(HttpClient::new(token).await?);
+
+    Message::create(
+        &ctx,
+        &channel_id,
+        CreateMessage::new("The build succeeded!"),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -1,7 +1,9 @@
 use {
-    anyhow::Result,
     async_trait::async_trait,
-    resvolt::{models::events::ReadyEvent, Client, Context, RevoltCommand, RevoltEventHandler},
+    resvolt::{
+        error::Result, models::events::ReadyEvent, Args, Client, CommandName, Context, HelpCommand,
+        RevoltCommand, RevoltEventHandler,
+    },
     std::{env, time::Duration},
 };
 
@@ -9,9 +11,8 @@ struct PingCommand;
 
 #[async_trait]
 impl RevoltCommand for PingCommand {
-    async fn execute(&self, ctx: &Context) -> Result<()> {
-        println!("{:?}", ctx.latency().await);
-        let msg_content = format!("Pong!\nWS Latency: {:?}", ctx.latency().await);
+    async fn execute(&self, ctx: &Context, _: Args) -> Result<()> {
+        let msg_content = format!("Pong!\nWS Latency: {:?}", ctx.latency().await?);
 
         if let Ok(msg) = ctx.msg().reply(ctx, msg_content, true).await {
             msg.delete_after(ctx, Duration::from_secs(3)).await.ok();
@@ -19,6 +20,10 @@ impl RevoltCommand for PingCommand {
 
         Ok(())
     }
+
+    fn description(&self) -> Option<&str> {
+        Some("Show the WebSocket latency")
+    }
 }
 
 struct EventHandler;
@@ -40,7 +45,8 @@ async fn main() {
         .unwrap();
 
     client
-        .register_command("ping".to_string(), PingCommand)
+        .register_command(CommandName::new("ping").case_sensitive(false), PingCommand)
         .await;
+    client.set_help_command(HelpCommand::new()).await;
     client.listen().await.unwrap();
 }
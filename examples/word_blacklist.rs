@@ -1,7 +1,7 @@
 use {
-    anyhow::Result,
     async_trait::async_trait,
     resvolt::{
+        error::Result,
         models::{events::ReadyEvent, Content, Message},
         state::State,
         Client, Context, RevoltEventHandler,
@@ -0,0 +1,60 @@
+use {rand::Rng, std::time::Duration};
+
+/// Exponential backoff with jitter, shared by the reconnection and retry policies.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Backoff {
+    min: Duration,
+    max: Duration,
+}
+
+impl Backoff {
+    /// Creates a new [`Backoff`] growing from `min` up to `max`.
+    pub(crate) fn new(min: Duration, max: Duration) -> Self {
+        Self { min, max }
+    }
+
+    /// Returns the delay to wait before the specified attempt (starting from `1`).
+    ///
+    /// Half of the delay is fixed and the other half is random, so that many clients
+    /// failing at once do not try again at the same time.
+    pub(crate) fn delay(&self, attempt: u32) -> Duration {
+        let half = self.ceiling(attempt) / 2;
+
+        half + rand::thread_rng().gen_range(Duration::ZERO..=half)
+    }
+
+    /// Returns the delay before the specified attempt, without jitter.
+    fn ceiling(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(16);
+
+        self.min.saturating_mul(1 << exponent).min(self.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grows_exponentially_up_to_max() {
+        let backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(60));
+
+        assert_eq!(backoff.ceiling(0), Duration::from_secs(1));
+        assert_eq!(backoff.ceiling(1), Duration::from_secs(1));
+        assert_eq!(backoff.ceiling(2), Duration::from_secs(2));
+        assert_eq!(backoff.ceiling(6), Duration::from_secs(32));
+        assert_eq!(backoff.ceiling(7), Duration::from_secs(60));
+        assert_eq!(backoff.ceiling(u32::MAX), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn delay_is_between_half_and_ceiling() {
+        let backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(60));
+
+        for attempt in 1..10 {
+            let delay = backoff.delay(attempt);
+            let ceiling = backoff.ceiling(attempt);
+            assert!(delay >= ceiling / 2 && delay <= ceiling);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+
+/// Builder for a file to upload to the file server (Autumn).
+#[derive(Debug, Clone)]
+pub struct CreateAttachment {
+    pub(crate) filename: String,
+    pub(crate) content_type: Option<String>,
+    pub(crate) data: AttachmentData,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum AttachmentData {
+    Bytes(Vec<u8>),
+    Path(PathBuf),
+}
+
+impl CreateAttachment {
+    /// Creates a new builder from the content of a file.
+    pub fn bytes(filename: impl Into<String>, data: impl Into<Vec<u8>>) -> Self {
+        Self {
+            filename: filename.into(),
+            content_type: None,
+            data: AttachmentData::Bytes(data.into()),
+        }
+    }
+
+    /// Creates a new builder from the path of a file, which is read when the file is uploaded.
+    pub fn path(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+
+        Self {
+            filename: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            content_type: None,
+            data: AttachmentData::Path(path),
+        }
+    }
+
+    /// Set the file name.
+    pub fn filename(mut self, filename: impl Into<String>) -> Self {
+        self.filename = filename.into();
+        self
+    }
+
+    /// Set the content type, guessed by the file server if not set.
+    pub fn content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
+}
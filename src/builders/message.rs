@@ -1,8 +1,10 @@
 use serde::Serialize;
 
 use crate::{
-    builders::CreateEmbed,
-    models::{Masquerade, ID},
+    builders::{CreateAttachment, CreateEmbed},
+    error::Result,
+    http::HttpClient,
+    models::{AttachmentTag, Interactions, Masquerade, MessageSort, ID},
 };
 
 /// Builder for create a message.
@@ -11,12 +13,16 @@ pub struct CreateMessage {
     content: String,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     attachments: Vec<ID>,
+    #[serde(skip)]
+    files: Vec<CreateAttachment>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     replies: Vec<Reply>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     embeds: Vec<CreateEmbed>,
     #[serde(skip_serializing_if = "Option::is_none")]
     masquerade: Option<Masquerade>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    interactions: Option<Interactions>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -31,9 +37,11 @@ impl CreateMessage {
         Self {
             content: content.into(),
             attachments: Vec::new(),
+            files: Vec::new(),
             replies: Vec::new(),
             embeds: Vec::new(),
             masquerade: None,
+            interactions: None,
         }
     }
 
@@ -43,6 +51,14 @@ impl CreateMessage {
         self
     }
 
+    /// Set a file to upload and include in the message.
+    ///
+    /// The file is uploaded when the message is sent.
+    pub fn file(mut self, file: CreateAttachment) -> Self {
+        self.files.push(file);
+        self
+    }
+
     /// Set a message to reply to.
     pub fn reply(mut self, id: &ID, mention: bool) -> Self {
         self.replies.push(Reply {
@@ -63,6 +79,24 @@ impl CreateMessage {
         self.masquerade = Some(masquerade);
         self
     }
+
+    /// Set the interactions, like the reactions added to the message.
+    pub fn interactions(mut self, interactions: Interactions) -> Self {
+        self.interactions = Some(interactions);
+        self
+    }
+}
+
+impl CreateMessage {
+    /// Upload the files and replace them with the ids of the attachments.
+    pub(crate) async fn upload_files(&mut self, http_client: &HttpClient) -> Result<()> {
+        for file in std::mem::take(&mut self.files) {
+            let id = http_client.upload(AttachmentTag::Attachments, file).await?;
+            self.attachments.push(id);
+        }
+
+        Ok(())
+    }
 }
 
 impl<T: Into<String>> From<T> for CreateMessage {
@@ -104,3 +138,67 @@ impl<T: Into<String>> From<T> for EditMessage {
         Self::new().content(content)
     }
 }
+
+/// Builder for search messages in a channel.
+#[derive(Debug, Clone, Serialize)]
+pub struct MessageSearch {
+    query: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    before: Option<ID>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    after: Option<ID>,
+    sort: MessageSort,
+    include_users: bool,
+}
+
+impl MessageSearch {
+    /// Creates a new builder, sorting the messages by relevance.
+    pub fn new(query: impl Into<String>) -> Self {
+        Self {
+            query: query.into(),
+            limit: None,
+            before: None,
+            after: None,
+            sort: MessageSort::Relevance,
+            include_users: false,
+        }
+    }
+
+    /// Set the maximum number of messages to return, between `1` and `100`.
+    pub fn limit(mut self, limit: u8) -> Self {
+        self.limit = Some(limit.clamp(1, 100));
+        self
+    }
+
+    /// Search only the messages sent before the specified message.
+    pub fn before(mut self, id: &ID) -> Self {
+        self.before = Some(id.clone());
+        self
+    }
+
+    /// Search only the messages sent after the specified message.
+    pub fn after(mut self, id: &ID) -> Self {
+        self.after = Some(id.clone());
+        self
+    }
+
+    /// Set the sort order, [`MessageSort::Relevance`] by default.
+    pub fn sort(mut self, sort: MessageSort) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Set whether the users and members are bundled with the messages.
+    pub fn include_users(mut self, include_users: bool) -> Self {
+        self.include_users = include_users;
+        self
+    }
+}
+
+impl<T: Into<String>> From<T> for MessageSearch {
+    fn from(query: T) -> Self {
+        Self::new(query)
+    }
+}
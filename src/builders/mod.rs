@@ -1,9 +1,11 @@
 //! Builders for create and edit Revolt API models.
 
-pub use {channel::*, embed::*, message::*, server::*, user::*};
+pub use {attachment::*, channel::*, embed::*, message::*, role::*, server::*, user::*};
 
+mod attachment;
 mod channel;
 mod embed;
 mod message;
+mod role;
 mod server;
 mod user;
@@ -0,0 +1,112 @@
+use serde::Serialize;
+
+use crate::models::{events::RoleField, Override, Permissions};
+
+/// Builder for create a role.
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateRole {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rank: Option<i64>,
+}
+
+impl CreateRole {
+    /// Creates a new builder.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            rank: None,
+        }
+    }
+
+    /// Set the rank.
+    pub fn rank(mut self, rank: i64) -> Self {
+        self.rank = Some(rank);
+        self
+    }
+}
+
+impl<T: Into<String>> From<T> for CreateRole {
+    fn from(name: T) -> Self {
+        Self::new(name)
+    }
+}
+
+/// Builder for edit a role.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EditRole {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    colour: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hoist: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rank: Option<i64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    remove: Vec<RoleField>,
+}
+
+impl EditRole {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the name.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the colour, any valid CSS colour.
+    pub fn colour(mut self, colour: impl Into<String>) -> Self {
+        self.colour = Some(colour.into());
+        self
+    }
+
+    /// Set whether the role is displayed separately in the member list.
+    pub fn hoist(mut self, hoist: bool) -> Self {
+        self.hoist = Some(hoist);
+        self
+    }
+
+    /// Set the rank.
+    pub fn rank(mut self, rank: i64) -> Self {
+        self.rank = Some(rank);
+        self
+    }
+
+    /// Set a role field to remove.
+    pub fn remove(mut self, field: RoleField) -> Self {
+        self.remove.push(field);
+        self
+    }
+}
+
+/// Body of the requests setting the permissions of a role or of everyone.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct SetPermissions {
+    permissions: PermissionsValue,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+enum PermissionsValue {
+    Override(Override),
+    Value(Permissions),
+}
+
+impl SetPermissions {
+    pub(crate) fn allow_deny(permissions: Override) -> Self {
+        Self {
+            permissions: PermissionsValue::Override(permissions),
+        }
+    }
+
+    pub(crate) fn value(permissions: Permissions) -> Self {
+        Self {
+            permissions: PermissionsValue::Value(permissions),
+        }
+    }
+}
@@ -93,8 +93,8 @@ pub struct EditMember {
     avatar: Option<ID>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     roles: Vec<ID>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    remove: Option<MemberField>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    remove: Vec<MemberField>,
 }
 
 impl EditMember {
@@ -123,7 +123,7 @@ impl EditMember {
 
     /// Set a member field to remove.
     pub fn remove(mut self, field: MemberField) -> Self {
-        self.remove = Some(field);
+        self.remove.push(field);
         self
     }
 }
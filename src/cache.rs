@@ -4,13 +4,16 @@
 //! Using caching reduces latency to access data and allows you to avoid requests to the API.
 
 use ahash::AHashMap;
-use {async_trait::async_trait, tokio::sync::RwLock};
+use {async_trait::async_trait, std::collections::VecDeque, tokio::sync::RwLock};
 
 use crate::{
-    models::{events::ServerEvent, Channel, Member, MemberID, Server, User, ID},
+    models::{events::ServerEvent, Channel, Member, MemberID, Message, Server, User, ID},
     Context,
 };
 
+/// The maximum number of messages kept in cache.
+pub const MESSAGE_CACHE_SIZE: usize = 1000;
+
 /// A cache containing data received from the API.
 #[derive(Debug, Default)]
 pub struct Cache {
@@ -18,6 +21,53 @@ pub struct Cache {
     pub(crate) channels: RwLock<AHashMap<ID, Channel>>,
     pub(crate) servers: RwLock<AHashMap<ID, Server>>,
     pub(crate) members: RwLock<AHashMap<MemberID, Member>>,
+    pub(crate) messages: RwLock<MessageCache>,
+}
+
+/// The most recent messages, the oldest ones are removed once the cache is full.
+#[derive(Debug, Default)]
+pub(crate) struct MessageCache {
+    messages: AHashMap<ID, Message>,
+    order: VecDeque<ID>,
+}
+
+impl MessageCache {
+    pub(crate) fn get(&self, id: &ID) -> Option<&Message> {
+        self.messages.get(id)
+    }
+
+    pub(crate) fn get_mut(&mut self, id: &ID) -> Option<&mut Message> {
+        self.messages.get_mut(id)
+    }
+
+    pub(crate) fn insert(&mut self, message: Message) {
+        if self
+            .messages
+            .insert(message.id.clone(), message.clone())
+            .is_some()
+        {
+            return;
+        }
+
+        self.order.push_back(message.id);
+
+        if self.order.len() > MESSAGE_CACHE_SIZE {
+            if let Some(id) = self.order.pop_front() {
+                self.messages.remove(&id);
+            }
+        }
+    }
+
+    pub(crate) fn remove(&mut self, id: &ID) {
+        if self.messages.remove(id).is_some() {
+            self.order.retain(|cached| cached != id);
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.messages.clear();
+        self.order.clear();
+    }
 }
 
 impl Cache {
@@ -25,6 +75,11 @@ impl Cache {
         match event {
             ServerEvent::Ready(event) => event.update(ctx).await,
             ServerEvent::Message(event) => event.update(ctx).await,
+            ServerEvent::MessageUpdate(event) => event.update(ctx).await,
+            ServerEvent::MessageDelete(event) => event.update(ctx).await,
+            ServerEvent::MessageReact(event) => event.update(ctx).await,
+            ServerEvent::MessageUnreact(event) => event.update(ctx).await,
+            ServerEvent::MessageRemoveReaction(event) => event.update(ctx).await,
             ServerEvent::ChannelCreate(event) => event.update(ctx).await,
             ServerEvent::ChannelUpdate(event) => event.update(ctx).await,
             ServerEvent::ChannelDelete(event) => event.update(ctx).await,
@@ -35,6 +90,8 @@ impl Cache {
             ServerEvent::ServerMemberUpdate(event) => event.update(ctx).await,
             ServerEvent::ServerMemberJoin(event) => event.update(ctx).await,
             ServerEvent::ServerMemberLeave(event) => event.update(ctx).await,
+            ServerEvent::ServerRoleUpdate(event) => event.update(ctx).await,
+            ServerEvent::ServerRoleDelete(event) => event.update(ctx).await,
             ServerEvent::UserUpdate(event) => event.update(ctx).await,
             _ => (),
         }
@@ -55,14 +112,8 @@ impl Cache {
         self.users
             .read()
             .await
-            .iter()
-            .filter_map(|(_, user)| {
-                if filter(user) {
-                    return Some(user);
-                }
-
-                None
-            })
+            .values()
+            .filter(|user| filter(user))
             .cloned()
             .collect()
     }
@@ -82,14 +133,8 @@ impl Cache {
         self.channels
             .read()
             .await
-            .iter()
-            .filter_map(|(_, channel)| {
-                if filter(channel) {
-                    return Some(channel);
-                }
-
-                None
-            })
+            .values()
+            .filter(|channel| filter(channel))
             .cloned()
             .collect()
     }
@@ -109,14 +154,8 @@ impl Cache {
         self.servers
             .read()
             .await
-            .iter()
-            .filter_map(|(_, server)| {
-                if filter(server) {
-                    return Some(server);
-                }
-
-                None
-            })
+            .values()
+            .filter(|server| filter(server))
             .cloned()
             .collect()
     }
@@ -126,6 +165,13 @@ impl Cache {
         self.servers.read().await.len()
     }
 
+    /// Get a message from cache.
+    ///
+    /// Only the last [`MESSAGE_CACHE_SIZE`] messages are kept in cache.
+    pub async fn message(&self, id: &ID) -> Option<Message> {
+        self.messages.read().await.get(id).cloned()
+    }
+
     /// Get a member from cache.
     pub async fn member(&self, id: &MemberID) -> Option<Member> {
         self.members.read().await.get(id).cloned()
@@ -141,14 +187,8 @@ impl Cache {
         self.members
             .read()
             .await
-            .iter()
-            .filter_map(|(_, member)| {
-                if filter(member) {
-                    return Some(member);
-                }
-
-                None
-            })
+            .values()
+            .filter(|member| filter(member))
             .cloned()
             .collect()
     }
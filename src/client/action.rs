@@ -6,8 +6,8 @@ use {
     },
 };
 
+use crate::error::{RSError, Result};
 use crate::models::events::ClientEvent;
-use anyhow::Result;
 
 #[derive(Debug)]
 pub enum Action {
@@ -38,24 +38,30 @@ impl ActionMessenger {
     pub async fn send(&self, event: ClientEvent) -> Result<()> {
         let (tx, rx) = oneshot::channel();
 
-        self.0.send(Action::SendEvent { event, tx }).unwrap();
+        self.0
+            .send(Action::SendEvent { event, tx })
+            .map_err(|_| RSError::GatewayUnavailable)?;
 
-        rx.await.unwrap()
+        rx.await.map_err(|_| RSError::GatewayUnavailable)?
     }
 
-    pub async fn latency(&self) -> Option<Duration> {
+    pub async fn latency(&self) -> Result<Option<Duration>> {
         let (tx, rx) = oneshot::channel();
 
-        self.0.send(Action::GetLatency { tx }).unwrap();
+        self.0
+            .send(Action::GetLatency { tx })
+            .map_err(|_| RSError::GatewayUnavailable)?;
 
-        rx.await.unwrap()
+        rx.await.map_err(|_| RSError::GatewayUnavailable)
     }
 
     pub async fn close(&self) -> Result<()> {
         let (tx, rx) = oneshot::channel();
 
-        self.0.send(Action::Close { tx }).unwrap();
+        self.0
+            .send(Action::Close { tx })
+            .map_err(|_| RSError::GatewayUnavailable)?;
 
-        rx.await.unwrap()
+        rx.await.map_err(|_| RSError::GatewayUnavailable)?
     }
 }
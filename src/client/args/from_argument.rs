@@ -0,0 +1,293 @@
+use {async_trait::async_trait, reqwest::StatusCode, std::time::Duration};
+
+use crate::{
+    error::{APIError, ArgumentError, RSError, Result},
+    models::{Channel, Member, Role, Server, User, ID},
+    Context,
+};
+
+/// A type that can be parsed from a command argument.
+#[async_trait]
+pub trait FromArgument: Sized + Send {
+    /// What the argument is, used in the errors, like `integer` or `user`.
+    const NAME: &'static str;
+
+    /// Parse the argument.
+    async fn from_argument(ctx: &Context, arg: &str) -> Result<Self>;
+}
+
+macro_rules! from_str_argument {
+    ($name:literal: $($ty:ty),+) => {
+        $(
+            #[async_trait]
+            impl FromArgument for $ty {
+                const NAME: &'static str = $name;
+
+                async fn from_argument(_: &Context, arg: &str) -> Result<Self> {
+                    arg.parse().map_err(|_| invalid::<Self>(arg))
+                }
+            }
+        )+
+    };
+}
+
+from_str_argument!("integer": i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+from_str_argument!("number": f32, f64);
+from_str_argument!("character": char);
+
+#[async_trait]
+impl FromArgument for String {
+    const NAME: &'static str = "text";
+
+    async fn from_argument(_: &Context, arg: &str) -> Result<Self> {
+        Ok(arg.to_owned())
+    }
+}
+
+/// Parsed from `true`, `yes`, `on`, `1` and `false`, `no`, `off`, `0`.
+#[async_trait]
+impl FromArgument for bool {
+    const NAME: &'static str = "boolean";
+
+    async fn from_argument(_: &Context, arg: &str) -> Result<Self> {
+        match arg.to_ascii_lowercase().as_str() {
+            "true" | "yes" | "y" | "on" | "1" => Ok(true),
+            "false" | "no" | "n" | "off" | "0" => Ok(false),
+            _ => Err(invalid::<Self>(arg)),
+        }
+    }
+}
+
+/// Parsed from numbers followed by units, like `90s` or `1h30m`, with `ms`, `s`, `m`, `h`, `d`
+/// and `w` units. Numbers without unit are seconds.
+#[async_trait]
+impl FromArgument for Duration {
+    const NAME: &'static str = "duration";
+
+    async fn from_argument(_: &Context, arg: &str) -> Result<Self> {
+        parse_duration(arg).ok_or_else(|| invalid::<Self>(arg))
+    }
+}
+
+/// Parsed from a mention or an id.
+#[async_trait]
+impl FromArgument for User {
+    const NAME: &'static str = "user";
+
+    async fn from_argument(ctx: &Context, arg: &str) -> Result<Self> {
+        let id = parse_id(arg, '@').ok_or_else(|| invalid::<Self>(arg))?;
+
+        User::fetch(ctx, &id)
+            .await
+            .map_err(|err| not_found::<Self>(arg, err))
+    }
+}
+
+/// Parsed from a mention or an id, in the server of the command.
+#[async_trait]
+impl FromArgument for Member {
+    const NAME: &'static str = "member";
+
+    async fn from_argument(ctx: &Context, arg: &str) -> Result<Self> {
+        let id = parse_id(arg, '@').ok_or_else(|| invalid::<Self>(arg))?;
+        let server_id = command_server_id(ctx)
+            .await?
+            .ok_or_else(|| invalid::<Self>(arg))?;
+
+        Member::fetch(ctx, &server_id, &id)
+            .await
+            .map_err(|err| not_found::<Self>(arg, err))
+    }
+}
+
+/// Parsed from a mention or an id.
+#[async_trait]
+impl FromArgument for Channel {
+    const NAME: &'static str = "channel";
+
+    async fn from_argument(ctx: &Context, arg: &str) -> Result<Self> {
+        let id = parse_id(arg, '#').ok_or_else(|| invalid::<Self>(arg))?;
+
+        Channel::fetch(ctx, &id)
+            .await
+            .map_err(|err| not_found::<Self>(arg, err))
+    }
+}
+
+/// Parsed from a mention, an id or a name, in the server of the command.
+#[async_trait]
+impl FromArgument for Role {
+    const NAME: &'static str = "role";
+
+    async fn from_argument(ctx: &Context, arg: &str) -> Result<Self> {
+        let server_id = command_server_id(ctx)
+            .await?
+            .ok_or_else(|| invalid::<Self>(arg))?;
+        let server = Server::fetch(ctx, &server_id).await?;
+
+        let role = match parse_id(arg, '%') {
+            Some(id) => server.roles.get(&id),
+            None => server
+                .roles
+                .values()
+                .find(|role| role.name.eq_ignore_ascii_case(arg)),
+        };
+
+        role.cloned().ok_or_else(|| {
+            ArgumentError::NotFound {
+                value: arg.to_owned(),
+                expected: Self::NAME,
+            }
+            .into()
+        })
+    }
+}
+
+fn invalid<T: FromArgument>(arg: &str) -> RSError {
+    ArgumentError::Invalid {
+        value: arg.to_owned(),
+        expected: T::NAME,
+    }
+    .into()
+}
+
+/// Turns the errors of the API meaning that the argument does not exist into an argument error.
+fn not_found<T: FromArgument>(arg: &str, err: RSError) -> RSError {
+    let not_found = matches!(
+        err.api_error(),
+        Some(APIError::NotFound | APIError::UnknownUser | APIError::UnknownChannel)
+    ) || err.status() == Some(StatusCode::NOT_FOUND);
+
+    if not_found {
+        ArgumentError::NotFound {
+            value: arg.to_owned(),
+            expected: T::NAME,
+        }
+        .into()
+    } else {
+        err
+    }
+}
+
+/// Returns the id of a mention like `<@id>`, or the argument itself if it is an id.
+fn parse_id(arg: &str, sigil: char) -> Option<ID> {
+    let id = arg
+        .strip_prefix('<')
+        .and_then(|arg| arg.strip_prefix(sigil))
+        .and_then(|arg| arg.strip_suffix('>'))
+        .unwrap_or(arg);
+
+    (id.len() == 26 && id.bytes().all(|byte| byte.is_ascii_alphanumeric()))
+        .then(|| id.to_ascii_uppercase())
+}
+
+fn parse_duration(arg: &str) -> Option<Duration> {
+    if let Ok(secs) = arg.parse() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    if arg.is_empty() {
+        return None;
+    }
+
+    let mut duration = Duration::ZERO;
+    let mut rest = arg;
+
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+        let (value, unit_rest) = rest.split_at(digits);
+        let unit_len = unit_rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(unit_rest.len());
+        let (unit, next) = unit_rest.split_at(unit_len);
+
+        let unit = match unit.to_ascii_lowercase().as_str() {
+            "ms" => Duration::from_millis(1),
+            "s" | "sec" | "secs" => Duration::from_secs(1),
+            "m" | "min" | "mins" => Duration::from_secs(60),
+            "h" | "hour" | "hours" => Duration::from_secs(60 * 60),
+            "d" | "day" | "days" => Duration::from_secs(24 * 60 * 60),
+            "w" | "week" | "weeks" => Duration::from_secs(7 * 24 * 60 * 60),
+            _ => return None,
+        };
+
+        duration = duration.checked_add(unit.checked_mul(value.parse().ok()?)?)?;
+        rest = next;
+    }
+
+    Some(duration)
+}
+
+/// Returns the id of the server where the command was sent, if any.
+async fn command_server_id(ctx: &Context) -> Result<Option<ID>> {
+    let Some(msg) = ctx.message() else {
+        return Ok(None);
+    };
+
+    let channel = Channel::fetch(ctx, &msg.channel_id).await?;
+
+    Ok(channel.server_id().cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_duration_parses_units() {
+        let cases = [
+            ("0", Duration::ZERO),
+            ("90", Duration::from_secs(90)),
+            ("90s", Duration::from_secs(90)),
+            ("250ms", Duration::from_millis(250)),
+            ("5m", Duration::from_secs(5 * 60)),
+            ("1h30m", Duration::from_secs(90 * 60)),
+            ("2d", Duration::from_secs(2 * 24 * 60 * 60)),
+            ("1w", Duration::from_secs(7 * 24 * 60 * 60)),
+            ("1H30M", Duration::from_secs(90 * 60)),
+            ("3mins", Duration::from_secs(3 * 60)),
+            ("1h1h", Duration::from_secs(2 * 60 * 60)),
+        ];
+
+        for (arg, expected) in cases {
+            assert_eq!(parse_duration(arg), Some(expected), "{arg:?}");
+        }
+    }
+
+    #[test]
+    fn parse_duration_rejects_invalid_durations() {
+        let cases = [
+            "",
+            "s",
+            "h1",
+            "1x",
+            "1 h",
+            "1.5h",
+            "-1s",
+            "10years",
+            "1h30",
+            "99999999999999999999",
+            "99999999999999999999s",
+            "18446744073709551615w",
+            "18446744073709551615s1s",
+        ];
+
+        for arg in cases {
+            assert_eq!(parse_duration(arg), None, "{arg:?}");
+        }
+    }
+
+    #[test]
+    fn parse_id_accepts_mentions_and_ids() {
+        let id = "01HZ8N7K3M5XTQW9RFYB2C4D6E";
+
+        assert_eq!(parse_id(id, '@').as_deref(), Some(id));
+        assert_eq!(parse_id(&id.to_lowercase(), '@').as_deref(), Some(id));
+        assert_eq!(parse_id(&format!("<@{id}>"), '@').as_deref(), Some(id));
+        assert_eq!(parse_id(&format!("<%{id}>"), '%').as_deref(), Some(id));
+        assert_eq!(parse_id(&format!("<#{id}>"), '@'), None);
+        assert_eq!(parse_id(&format!("<@{id}"), '@'), None);
+        assert_eq!(parse_id("01HZ8N7K3M", '@'), None);
+        assert_eq!(parse_id("01HZ8N7K3M5XTQW9RFYB2C4D6!", '@'), None);
+    }
+}
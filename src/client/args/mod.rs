@@ -0,0 +1,322 @@
+pub use from_argument::*;
+
+mod from_argument;
+
+use crate::{
+    error::{ArgumentError, Result},
+    Context,
+};
+
+/// The arguments of a command, consumed from left to right.
+///
+/// Arguments are separated by whitespace and can be wrapped in double quotes to include
+/// whitespace, like `"hello world"`. Flags like `--silent` or `--reason spam` can appear anywhere.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Args {
+    input: String,
+    offset: usize,
+}
+
+/// An argument and its position in the input.
+struct Token {
+    value: String,
+    quoted: bool,
+    start: usize,
+    end: usize,
+}
+
+impl Args {
+    /// Creates new arguments from the text following the command name.
+    pub fn new(input: impl Into<String>) -> Self {
+        Self {
+            input: input.into(),
+            offset: 0,
+        }
+    }
+
+    /// Returns whether all the arguments were consumed.
+    pub fn is_empty(&self) -> bool {
+        self.remaining().is_empty()
+    }
+
+    /// Returns the text of the arguments that are not consumed yet.
+    pub fn remaining(&self) -> &str {
+        self.input[self.offset..].trim()
+    }
+
+    /// Returns the next argument without consuming it.
+    pub fn peek(&self) -> Option<String> {
+        self.token_at(self.offset).map(|token| token.value)
+    }
+
+    /// Consume the rest of the arguments as a single line of text.
+    pub fn rest(&mut self) -> Option<String> {
+        let rest = self.remaining().to_owned();
+        self.offset = self.input.len();
+
+        (!rest.is_empty()).then_some(rest)
+    }
+
+    /// Consume and parse the next argument.
+    ///
+    /// Fails with [`ArgumentError::Missing`] if there are no arguments left.
+    pub async fn parse<T: FromArgument>(&mut self, ctx: &Context) -> Result<T> {
+        match self.parse_optional(ctx).await? {
+            Some(value) => Ok(value),
+            None => Err(ArgumentError::Missing { expected: T::NAME }.into()),
+        }
+    }
+
+    /// Consume and parse the next argument, if any.
+    pub async fn parse_optional<T: FromArgument>(&mut self, ctx: &Context) -> Result<Option<T>> {
+        match self.next() {
+            Some(arg) => T::from_argument(ctx, &arg).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Remove a flag like `--silent` from the arguments and return whether it was found.
+    pub fn flag(&mut self, name: &str) -> bool {
+        let flag = format!("--{}", name);
+        let token = self
+            .tokens()
+            .find(|token| !token.quoted && token.value == flag);
+
+        match token {
+            Some(token) => {
+                self.input.replace_range(token.start..token.end, "");
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove an option like `--reason spam` or `--reason=spam` from the arguments and return
+    /// its value.
+    pub fn option(&mut self, name: &str) -> Option<String> {
+        let flag = format!("--{}", name);
+        let (start, end, value) = {
+            let mut tokens = self.tokens().skip_while(|token| {
+                token.quoted || token.value.split('=').next() != Some(flag.as_str())
+            });
+
+            let token = tokens.next()?;
+            match token.value.split_once('=') {
+                Some((_, value)) => (token.start, token.end, value.to_owned()),
+                None => {
+                    let value = tokens.next()?;
+                    (token.start, value.end, value.value)
+                }
+            }
+        };
+
+        self.input.replace_range(start..end, "");
+
+        Some(value)
+    }
+
+    /// Fails with [`ArgumentError::Unexpected`] if some arguments were not consumed.
+    pub fn finish(&self) -> Result<()> {
+        match self.is_empty() {
+            true => Ok(()),
+            false => Err(ArgumentError::Unexpected(self.remaining().to_owned()).into()),
+        }
+    }
+
+    /// Returns the arguments that are not consumed yet.
+    fn tokens(&self) -> impl Iterator<Item = Token> + '_ {
+        let mut offset = self.offset;
+
+        std::iter::from_fn(move || {
+            let token = self.token_at(offset)?;
+            offset = token.end;
+
+            Some(token)
+        })
+    }
+
+    /// Returns the argument starting at the specified position, ignoring the whitespace.
+    fn token_at(&self, offset: usize) -> Option<Token> {
+        let rest = &self.input[offset..];
+        let start = offset + rest.len() - rest.trim_start().len();
+        let text = &self.input[start..];
+
+        let Some(quoted) = text.strip_prefix('"') else {
+            let len = text.find(char::is_whitespace).unwrap_or(text.len());
+
+            return (len > 0).then(|| Token {
+                value: text[..len].to_owned(),
+                quoted: false,
+                start,
+                end: start + len,
+            });
+        };
+
+        let mut value = String::new();
+        let mut chars = quoted.char_indices();
+        let mut end = self.input.len();
+
+        while let Some((index, char)) = chars.next() {
+            match char {
+                '\\' => value.extend(chars.next().map(|(_, char)| char)),
+                '"' => {
+                    end = start + 1 + index + 1;
+                    break;
+                }
+                char => value.push(char),
+            }
+        }
+
+        Some(Token {
+            value,
+            quoted: true,
+            start,
+            end,
+        })
+    }
+}
+
+impl Iterator for Args {
+    type Item = String;
+
+    /// Consume the next argument.
+    fn next(&mut self) -> Option<Self::Item> {
+        let token = self.token_at(self.offset)?;
+        self.offset = token.end;
+
+        Some(token.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(input: &str) -> Vec<String> {
+        Args::new(input).collect()
+    }
+
+    #[test]
+    fn splits_on_whitespace() {
+        assert_eq!(collect("ban  @user\tspam\n"), ["ban", "@user", "spam"]);
+        assert_eq!(collect("héllo wörld 👍"), ["héllo", "wörld", "👍"]);
+    }
+
+    #[test]
+    fn empty_input_has_no_arguments() {
+        for input in ["", "   ", "\n\t"] {
+            let mut args = Args::new(input);
+
+            assert!(args.is_empty());
+            assert_eq!(args.peek(), None);
+            assert_eq!(args.rest(), None);
+            assert_eq!(args.next(), None);
+            assert!(args.finish().is_ok());
+        }
+    }
+
+    #[test]
+    fn quotes_group_whitespace() {
+        assert_eq!(
+            collect(r#"say "hello world" now"#),
+            ["say", "hello world", "now"]
+        );
+        assert_eq!(collect(r#""" empty"#), ["", "empty"]);
+        assert_eq!(collect(r#"a"b c"#), [r#"a"b"#, "c"]);
+    }
+
+    #[test]
+    fn escaped_quotes_are_kept() {
+        assert_eq!(
+            collect(r#""say \"hi\"" "back\\slash" "\x""#),
+            [r#"say "hi""#, r"back\slash", "x"]
+        );
+    }
+
+    #[test]
+    fn unterminated_quotes_take_the_rest() {
+        assert_eq!(collect(r#"say "hello world"#), ["say", "hello world"]);
+        assert_eq!(collect(r#"say ""#), ["say", ""]);
+        assert_eq!(collect(r#"say "trailing\"#), ["say", "trailing"]);
+
+        let mut args = Args::new(r#"say "hello world"#);
+        args.next();
+        args.next();
+        assert!(args.is_empty());
+    }
+
+    #[test]
+    fn peek_does_not_consume() {
+        let mut args = Args::new("first second");
+
+        assert_eq!(args.peek().as_deref(), Some("first"));
+        assert_eq!(args.peek().as_deref(), Some("first"));
+        assert_eq!(args.next().as_deref(), Some("first"));
+        assert_eq!(args.remaining(), "second");
+    }
+
+    #[test]
+    fn rest_consumes_everything() {
+        let mut args = Args::new(r#"ban  spamming "a lot"  "#);
+        args.next();
+
+        assert_eq!(args.rest().as_deref(), Some(r#"spamming "a lot""#));
+        assert!(args.is_empty());
+        assert_eq!(args.rest(), None);
+    }
+
+    #[test]
+    fn flags_are_removed_anywhere() {
+        let mut args = Args::new(r#"--silent ban "--force" user --silent"#);
+
+        assert!(args.flag("silent"));
+        assert!(!args.flag("force"));
+        assert!(!args.flag("sil"));
+        assert_eq!(
+            args.collect::<Vec<_>>(),
+            ["ban", "--force", "user", "--silent"]
+        );
+    }
+
+    #[test]
+    fn flags_ignore_consumed_arguments() {
+        let mut args = Args::new("--silent ban");
+        args.next();
+
+        assert!(!args.flag("silent"));
+        assert_eq!(args.remaining(), "ban");
+    }
+
+    #[test]
+    fn options_take_a_value() {
+        let mut args = Args::new(r#"ban user --reason "too much spam" --days=7 --empty="#);
+
+        assert_eq!(args.option("reason").as_deref(), Some("too much spam"));
+        assert_eq!(args.option("days").as_deref(), Some("7"));
+        assert_eq!(args.option("empty").as_deref(), Some(""));
+        assert_eq!(args.option("missing"), None);
+        assert_eq!(args.collect::<Vec<_>>(), ["ban", "user"]);
+    }
+
+    #[test]
+    fn options_without_value_are_kept() {
+        let mut args = Args::new("ban --reason");
+
+        assert_eq!(args.option("reason"), None);
+        assert_eq!(args.remaining(), "ban --reason");
+    }
+
+    #[test]
+    fn finish_rejects_remaining_arguments() {
+        let mut args = Args::new("one two");
+        args.next();
+
+        match args.finish() {
+            Err(crate::error::RSError::Argument {
+                error: ArgumentError::Unexpected(rest),
+                ..
+            }) => assert_eq!(rest, "two"),
+            result => panic!("unexpected result: {result:?}"),
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use {std::sync::Arc, tokio::sync::RwLock};
+
+use super::CommandRegistry;
+use crate::{
+    error::RSError,
+    http::{Endpoints, HttpClient, RetryPolicy},
+    state::State,
+    websocket::WebSocketClient,
+    ActionMessenger, Client, ConnectionState, Context, PrefixResolver, ReconnectPolicy,
+    RevoltEventHandler, WireFormat,
+};
+
+use crate::error::Result;
+
+#[cfg(feature = "reaction_roles")]
+use crate::reaction_roles::ReactionRoles;
+
+/// Builder for a [`Client`].
+///
+/// Allows connecting to a self-hosted Revolt instance by changing the [`Endpoints`].
+pub struct ClientBuilder<T: RevoltEventHandler> {
+    token: String,
+    event_handler: T,
+    prefix_resolver: Box<dyn PrefixResolver>,
+    mention_prefix: bool,
+    dm_without_prefix: bool,
+    state: Option<Arc<State>>,
+    endpoints: Endpoints,
+    api_url: Option<String>,
+    ws_url: Option<String>,
+    autumn_url: Option<String>,
+    discover: bool,
+    https_only: bool,
+    wire_format: WireFormat,
+    reconnect_policy: ReconnectPolicy,
+    retry_policy: RetryPolicy,
+    #[cfg(feature = "reaction_roles")]
+    reaction_roles: Option<ReactionRoles>,
+}
+
+impl<T: RevoltEventHandler> ClientBuilder<T> {
+    /// Creates a new builder with the `!` command prefix and the official Revolt [`Endpoints`].
+    pub fn new(token: impl Into<String>, event_handler: T) -> Self {
+        Self {
+            token: token.into(),
+            event_handler,
+            prefix_resolver: Box::new(String::from("!")),
+            mention_prefix: false,
+            dm_without_prefix: false,
+            state: None,
+            endpoints: Endpoints::default(),
+            api_url: None,
+            ws_url: None,
+            autumn_url: None,
+            discover: false,
+            https_only: true,
+            wire_format: WireFormat::default(),
+            reconnect_policy: ReconnectPolicy::default(),
+            retry_policy: RetryPolicy::default(),
+            #[cfg(feature = "reaction_roles")]
+            reaction_roles: None,
+        }
+    }
+
+    /// Set the command prefix.
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix_resolver = Box::new(prefix.into());
+        self
+    }
+
+    /// Set the [`PrefixResolver`] returning the command prefixes of each message.
+    pub fn prefix_resolver(mut self, prefix_resolver: impl PrefixResolver) -> Self {
+        self.prefix_resolver = Box::new(prefix_resolver);
+        self
+    }
+
+    /// Set whether mentioning the bot can be used as a prefix, like `@bot ping`, `false` by default.
+    pub fn mention_prefix(mut self, mention_prefix: bool) -> Self {
+        self.mention_prefix = mention_prefix;
+        self
+    }
+
+    /// Set whether commands can be used without prefix in DMs, `false` by default.
+    ///
+    /// Only the messages starting with the name of a command are handled as commands,
+    /// the other ones are passed to the event handler.
+    pub fn dm_without_prefix(mut self, dm_without_prefix: bool) -> Self {
+        self.dm_without_prefix = dm_without_prefix;
+        self
+    }
+
+    /// Set the [`State`] shared with the handlers.
+    pub fn state(mut self, state: Arc<State>) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// Set all the [`Endpoints`] at once.
+    pub fn endpoints(mut self, endpoints: Endpoints) -> Self {
+        self.endpoints = endpoints;
+        self
+    }
+
+    /// Set the url of the REST API (Delta).
+    pub fn api_url(mut self, url: impl Into<String>) -> Self {
+        self.api_url = Some(url.into());
+        self
+    }
+
+    /// Set the url of the WebSocket API (Bonfire).
+    pub fn ws_url(mut self, url: impl Into<String>) -> Self {
+        self.ws_url = Some(url.into());
+        self
+    }
+
+    /// Set the url of the file server (Autumn).
+    pub fn autumn_url(mut self, url: impl Into<String>) -> Self {
+        self.autumn_url = Some(url.into());
+        self
+    }
+
+    /// Set whether the WebSocket and file server urls are discovered from the root of the REST API.
+    ///
+    /// Urls set explicitly with [`ws_url`](Self::ws_url) or [`autumn_url`](Self::autumn_url)
+    /// take precedence over the discovered ones.
+    pub fn discover_endpoints(mut self, discover: bool) -> Self {
+        self.discover = discover;
+        self
+    }
+
+    /// Set whether only secure connections (`https` and `wss`) are allowed, `true` by default.
+    ///
+    /// Disable it to connect to a local instance without TLS.
+    pub fn https_only(mut self, https_only: bool) -> Self {
+        self.https_only = https_only;
+        self
+    }
+
+    /// Set the [`WireFormat`] used to communicate with the WebSocket API.
+    pub fn wire_format(mut self, format: WireFormat) -> Self {
+        self.wire_format = format;
+        self
+    }
+
+    /// Set the [`ReconnectPolicy`] used when the connection is lost.
+    pub fn reconnect_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect_policy = policy;
+        self
+    }
+
+    /// Set the [`RetryPolicy`] used when a request to the REST API fails.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Set the [`ReactionRoles`] handled by the client.
+    #[cfg(feature = "reaction_roles")]
+    pub fn reaction_roles(mut self, reaction_roles: ReactionRoles) -> Self {
+        self.reaction_roles = Some(reaction_roles);
+        self
+    }
+
+    /// Resolve the endpoints, create the client and connect to the server.
+    pub async fn build(self) -> Result<Client<T>> {
+        let endpoints = Arc::new(self.resolve_endpoints().await?);
+
+        let http_client = HttpClient::connect(
+            self.token,
+            endpoints.clone(),
+            self.https_only,
+            self.retry_policy,
+        )
+        .await?;
+
+        let ws_client = WebSocketClient::connect(&endpoints.ws, self.wire_format).await?;
+        let (messenger, action_rx) = ActionMessenger::new();
+        let partial_context = Context::new(http_client, messenger, self.state.unwrap_or_default());
+
+        let command_registry = Arc::new(RwLock::new(CommandRegistry::new(
+            self.prefix_resolver,
+            self.mention_prefix,
+            self.dm_without_prefix,
+        )));
+
+        #[cfg(feature = "reaction_roles")]
+        let reaction_roles = self
+            .reaction_roles
+            .map(|roles| super::spawn_reaction_roles(roles, command_registry.clone()));
+
+        Ok(Client {
+            event_handler: Arc::new(self.event_handler),
+            command_registry,
+            ws_client,
+            action_rx,
+            partial_context,
+            reconnect_policy: self.reconnect_policy,
+            connection_state: ConnectionState::Connected,
+            authenticated_once: false,
+            #[cfg(feature = "reaction_roles")]
+            reaction_roles,
+        })
+    }
+
+    async fn resolve_endpoints(&self) -> Result<Endpoints> {
+        let api = self.api_url.as_ref().unwrap_or(&self.endpoints.api);
+
+        let mut endpoints = if self.discover {
+            Endpoints::discover(api, self.https_only).await?
+        } else {
+            Endpoints {
+                api: api.clone(),
+                ..self.endpoints.clone()
+            }
+        };
+
+        if let Some(ref ws) = self.ws_url {
+            endpoints.ws = ws.clone();
+        }
+
+        if let Some(ref autumn) = self.autumn_url {
+            endpoints.autumn = autumn.clone();
+        }
+
+        let endpoints = Endpoints::new(endpoints.api, endpoints.ws, endpoints.autumn);
+
+        if endpoints.autumn.is_empty() {
+            return Err(RSError::Config(
+                "The instance does not have a file server (Autumn) enabled, set its url explicitly"
+                    .into(),
+            ));
+        }
+
+        if self.https_only && !endpoints.ws.starts_with("wss://") {
+            return Err(RSError::Config(format!(
+                "Insecure WebSocket url while only secure connections are allowed: {}",
+                endpoints.ws
+            )));
+        }
+
+        Ok(endpoints)
+    }
+}
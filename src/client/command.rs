@@ -1,11 +1,121 @@
-use anyhow::Result;
+use crate::error::Result;
+use ahash::AHashMap;
 use async_trait::async_trait;
 
-use crate::Context;
+use crate::{Args, Context};
 
 /// A trait for commands
 #[async_trait]
 pub trait RevoltCommand: Send + Sync + 'static {
     /// The command code
-    async fn execute(&self, ctx: &Context) -> Result<()>;
+    async fn execute(&self, ctx: &Context, args: Args) -> Result<()>;
+
+    /// Whether the caller can run the command, checked before executing it and when listing
+    /// the commands in the [`HelpCommand`](crate::HelpCommand)
+    async fn check(&self, _ctx: &Context) -> Result<bool> {
+        Ok(true)
+    }
+
+    /// A short description of the command, shown in the help
+    fn description(&self) -> Option<&str> {
+        None
+    }
+
+    /// The arguments of the command, like `<user> [reason]`, shown when they cannot be parsed
+    fn usage(&self) -> Option<&str> {
+        None
+    }
+
+    /// Example arguments of the command, like `@user spam`, shown in the help
+    fn examples(&self) -> &[&str] {
+        &[]
+    }
+
+    /// The category of the command, used to group the commands in the help
+    fn category(&self) -> Option<&str> {
+        None
+    }
+
+    /// Whether the command is hidden from the help
+    fn hidden(&self) -> bool {
+        false
+    }
+
+    /// The subcommands of the command, like the ones of a [`CommandGroup`](crate::CommandGroup)
+    fn subcommands(&self) -> Vec<(&str, &dyn RevoltCommand)> {
+        Vec::new()
+    }
+
+    /// The subcommand with the specified name or alias, and its name
+    fn subcommand(&self, name: &str) -> Option<(&str, &dyn RevoltCommand)> {
+        self.subcommands()
+            .into_iter()
+            .find(|(subcommand_name, _)| *subcommand_name == name)
+    }
+}
+
+/// The name of a command, with its aliases and whether they are case sensitive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandName {
+    pub(crate) name: String,
+    pub(crate) aliases: Vec<String>,
+    case_sensitive: bool,
+}
+
+impl CommandName {
+    /// Creates a new case sensitive command name without aliases
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            aliases: Vec::new(),
+            case_sensitive: true,
+        }
+    }
+
+    /// Add an alias
+    pub fn alias(mut self, alias: impl Into<String>) -> Self {
+        self.aliases.push(alias.into());
+        self
+    }
+
+    /// Set whether the name and the aliases are case sensitive, `true` by default
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Returns the name and the aliases
+    pub(crate) fn names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.name.as_str()).chain(self.aliases.iter().map(String::as_str))
+    }
+
+    /// Whether the specified name is the name or an alias
+    pub(crate) fn matches(&self, name: &str) -> bool {
+        self.names().any(|candidate| match self.case_sensitive {
+            true => candidate == name,
+            false => candidate.to_lowercase() == name.to_lowercase(),
+        })
+    }
+}
+
+impl<T: Into<String>> From<T> for CommandName {
+    fn from(name: T) -> Self {
+        Self::new(name)
+    }
+}
+
+/// A command and its names
+pub(crate) struct RegisteredCommand {
+    pub(crate) name: CommandName,
+    pub(crate) command: Box<dyn RevoltCommand>,
+}
+
+/// Returns the command with the specified name or alias, from commands keyed by their name
+pub(crate) fn find_command<'a>(
+    commands: &'a AHashMap<String, RegisteredCommand>,
+    name: &str,
+) -> Option<&'a RegisteredCommand> {
+    commands
+        .get(name)
+        .or_else(|| commands.values().find(|command| command.name.matches(name)))
 }
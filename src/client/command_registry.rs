@@ -1,27 +1,145 @@
-use crate::{error::RSError, Context, RevoltCommand, RevoltErrorHandler};
+use crate::error::Result;
+use crate::{
+    builders::CreateMessage,
+    client::{
+        command::{find_command, RegisteredCommand},
+        group::join_usage,
+    },
+    error::RSError,
+    models::{Channel, Message},
+    Args, CommandName, Context, HelpCommand, PrefixResolver, RevoltCommand, RevoltErrorHandler,
+};
 use ahash::AHashMap;
-use anyhow::Result;
+
+/// The maximum number of suggestions when a command is not found
+const MAX_SUGGESTIONS: usize = 3;
 
 /// A command registry
 pub(crate) struct CommandRegistry {
-    pub(crate) prefix: String,
-    commands: AHashMap<String, Box<dyn RevoltCommand>>,
+    prefix_resolver: Box<dyn PrefixResolver>,
+    mention_prefix: bool,
+    dm_without_prefix: bool,
+    commands: AHashMap<String, RegisteredCommand>,
     error_handler: Option<Box<dyn RevoltErrorHandler>>,
+    help_command: Option<HelpCommand>,
 }
 
 impl CommandRegistry {
     /// Creates a new [`CommandRegistry`]
-    pub(crate) fn new(prefix: String) -> Self {
+    pub(crate) fn new(
+        prefix_resolver: Box<dyn PrefixResolver>,
+        mention_prefix: bool,
+        dm_without_prefix: bool,
+    ) -> Self {
         Self {
-            prefix,
+            prefix_resolver,
+            mention_prefix,
+            dm_without_prefix,
             commands: AHashMap::new(),
             error_handler: None,
+            help_command: None,
         }
     }
 
+    /// Sets the specified [`PrefixResolver`] in the command registry
+    pub(crate) fn set_prefix_resolver(&mut self, prefix_resolver: impl PrefixResolver) {
+        self.prefix_resolver = Box::new(prefix_resolver);
+    }
+
+    /// Returns the prefix and the arguments of the message, if it is a command
+    pub(crate) async fn strip_prefix(
+        &self,
+        ctx: &Context,
+        msg: &Message,
+        content: &str,
+    ) -> Result<Option<(String, Args)>> {
+        let content = content.trim_start();
+        let bot_id = &ctx.http_client.bot().id;
+        let mention = format!("<@{}>", bot_id);
+
+        let mut prefixes = self.prefix_resolver.prefixes(ctx, msg).await?;
+        if self.mention_prefix {
+            prefixes.push(mention.clone());
+        }
+
+        let prefix = prefixes
+            .into_iter()
+            .filter(|prefix| content.starts_with(prefix.as_str()))
+            .max_by_key(String::len);
+
+        if let Some(prefix) = prefix {
+            let args = Args::new(&content[prefix.len()..]);
+
+            // The mention is followed by a space in the usage and the help, like `@bot ping`.
+            return match prefix == mention {
+                true => Ok(Some((format!("{} ", prefix), args))),
+                false => Ok(Some((prefix, args))),
+            };
+        }
+
+        // Without prefix, only the messages naming a command are commands, so that the other
+        // messages reach the event handler. The messages of the bot itself are never commands.
+        if self.dm_without_prefix && &msg.author_id != bot_id {
+            let args = Args::new(content);
+            let is_command = args.peek().is_some_and(|name| self.is_command(&name));
+
+            if is_command {
+                if let Channel::DirectMessage(_) = Channel::fetch(ctx, &msg.channel_id).await? {
+                    return Ok(Some((String::new(), args)));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
     /// Registers a new [`RevoltCommand`] and its name to the command registry
-    pub(crate) fn register_command(&mut self, command_name: String, command: impl RevoltCommand) {
-        self.commands.insert(command_name, Box::new(command));
+    pub(crate) fn register_command(&mut self, name: CommandName, command: impl RevoltCommand) {
+        let command = RegisteredCommand {
+            name,
+            command: Box::new(command),
+        };
+
+        self.commands.insert(command.name.name.clone(), command);
+    }
+
+    /// Returns the command with the specified name or alias
+    fn command(&self, name: &str) -> Option<&RegisteredCommand> {
+        find_command(&self.commands, name)
+    }
+
+    /// Whether the specified name is the name or an alias of a command, or the help command
+    fn is_command(&self, name: &str) -> bool {
+        self.command(name).is_some()
+            || self
+                .help_command
+                .as_ref()
+                .is_some_and(|help_command| help_command.name == name)
+    }
+
+    /// Returns a [`RSError::CommandNotFound`] with the closest names to the specified one
+    fn not_found(&self, prefix: &str, name: String) -> RSError {
+        let help_command = self.help_command.iter().map(|help| help.name.as_str());
+        let names = self
+            .commands
+            .values()
+            .filter(|command| !command.command.hidden())
+            .flat_map(|command| command.name.names())
+            .chain(help_command);
+
+        let mut suggestions: Vec<_> = names
+            .filter_map(|candidate| Some((suggestion_distance(&name, candidate)?, candidate)))
+            .collect();
+        suggestions.sort_unstable();
+
+        RSError::CommandNotFound {
+            name,
+            suggestions: suggestions
+                .into_iter()
+                .take(MAX_SUGGESTIONS)
+                .map(|(_, candidate)| format!("{}{}", prefix, candidate))
+                .collect(),
+        }
     }
 
     /// Sets the specified [`RevoltErrorHandler`] in the command registry
@@ -29,29 +147,291 @@ impl CommandRegistry {
         self.error_handler = Some(Box::new(error_handler));
     }
 
-    /// Executes a specified command by its specified name
-    pub(crate) async fn execute_command(&self, command_name: &str, ctx: &Context) -> Result<()> {
-        if let Some(command) = self.commands.get(command_name) {
-            command.execute(ctx).await
+    /// Sets the specified [`HelpCommand`] in the command registry
+    pub(crate) fn set_help_command(&mut self, help_command: HelpCommand) {
+        self.help_command = Some(help_command);
+    }
+
+    /// Executes the command named by the first argument with the remaining arguments
+    pub(crate) async fn execute_command(
+        &self,
+        ctx: &Context,
+        prefix: &str,
+        mut args: Args,
+    ) -> Result<()> {
+        let command_name = args.next().unwrap_or_default();
+
+        if let Some(ref help_command) = self.help_command {
+            if help_command.name == command_name {
+                return self.help(ctx, prefix, help_command, args).await;
+            }
+        }
+
+        if let Some(RegisteredCommand { name, command }) = self.command(&command_name) {
+            if !command.check(ctx).await? {
+                return Err(RSError::CheckFailed(name.name.clone()));
+            }
+
+            match command.execute(ctx, args).await {
+                Err(RSError::Argument { error, usage }) => Err(RSError::Argument {
+                    error,
+                    usage: Some(join_usage(
+                        &format!("{}{}", prefix, command_name),
+                        usage.as_deref().or(command.usage()),
+                    )),
+                }),
+                result => result,
+            }
         } else {
-            self.handle_error(
-                ctx,
-                RSError::CommandNotFound(format!("Command '{}' not found", command_name)).into(),
-            )
-            .await
-            .ok();
+            self.handle_error(ctx, self.not_found(prefix, command_name))
+                .await
+                .ok();
 
             Ok(())
         }
     }
 
-    pub(crate) async fn handle_error(&self, ctx: &Context, error: anyhow::Error) -> Result<()> {
+    /// Replies with the list of the commands, or the detailed help of the command in the arguments
+    async fn help(
+        &self,
+        ctx: &Context,
+        prefix: &str,
+        help_command: &HelpCommand,
+        mut args: Args,
+    ) -> Result<()> {
+        let builder = match args.next() {
+            Some(command_name) => {
+                let registered = match self.command(&command_name) {
+                    Some(registered) if is_visible(ctx, registered.command.as_ref()).await => {
+                        registered
+                    }
+                    _ => return Err(self.not_found(prefix, command_name)),
+                };
+                let mut command = registered.command.as_ref();
+                let mut invocation = format!("{}{}", prefix, registered.name.name);
+                let mut aliases = &registered.name.aliases[..];
+
+                for name in args {
+                    let (subcommand_name, subcommand) = match command.subcommand(&name) {
+                        Some((subcommand_name, subcommand))
+                            if is_visible(ctx, subcommand).await =>
+                        {
+                            (subcommand_name, subcommand)
+                        }
+                        _ => {
+                            return Err(RSError::CommandNotFound {
+                                name: format!("{invocation} {name}"),
+                                suggestions: Vec::new(),
+                            })
+                        }
+                    };
+                    command = subcommand;
+                    invocation = format!("{invocation} {subcommand_name}");
+                    aliases = &[];
+                }
+
+                let mut subcommands = Vec::new();
+                for (name, subcommand) in command.subcommands() {
+                    if is_visible(ctx, subcommand).await {
+                        subcommands.push((name, subcommand));
+                    }
+                }
+
+                let usage = join_usage(&invocation, command.usage());
+
+                CreateMessage::new("").embed(|embed| {
+                    help_command.command(embed, &invocation, &usage, aliases, command, &subcommands)
+                })
+            }
+            None => {
+                let mut commands = Vec::new();
+                for (command_name, registered) in &self.commands {
+                    if is_visible(ctx, registered.command.as_ref()).await {
+                        commands.push((command_name.as_str(), registered.command.as_ref()));
+                    }
+                }
+
+                CreateMessage::new("").embed(|embed| help_command.list(embed, prefix, &commands))
+            }
+        };
+
+        ctx.msg().reply(ctx, builder, false).await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn handle_error(&self, ctx: &Context, error: RSError) -> Result<()> {
         if let Some(error_handler) = &self.error_handler {
             error_handler.handle(ctx, error).await;
 
             Ok(())
         } else {
-            Err(anyhow::Error::new(RSError::ErrorHandlerNotSet))
+            Err(RSError::ErrorHandlerNotSet)
+        }
+    }
+}
+
+/// Whether a command is shown to the caller in the help
+async fn is_visible(ctx: &Context, command: &dyn RevoltCommand) -> bool {
+    !command.hidden() && command.check(ctx).await.unwrap_or(false)
+}
+
+/// The edit distance between a name and a candidate, if it is close enough to suggest the
+/// candidate: at most one edit for every three characters of the name, ignoring the case
+fn suggestion_distance(name: &str, candidate: &str) -> Option<usize> {
+    let max_distance = name.chars().count().div_ceil(3).max(1);
+    let distance = edit_distance(&name.to_lowercase(), &candidate.to_lowercase());
+
+    (distance <= max_distance).then_some(distance)
+}
+
+/// The number of insertions, deletions, substitutions and transpositions of adjacent characters
+/// needed to turn a string into another one
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<_> = a.chars().collect();
+    let b: Vec<_> = b.chars().collect();
+
+    // Distances between the prefixes of `a` and `b`, for the last three rows.
+    let mut before_previous = vec![0; b.len() + 1];
+    let mut previous: Vec<_> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before_previous[j - 2] + 1);
+            }
         }
+
+        std::mem::swap(&mut before_previous, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, async_trait::async_trait};
+
+    struct Noop(bool);
+
+    #[async_trait]
+    impl RevoltCommand for Noop {
+        async fn execute(&self, _: &Context, _: Args) -> Result<()> {
+            Ok(())
+        }
+
+        fn hidden(&self) -> bool {
+            self.0
+        }
+    }
+
+    #[test]
+    fn edit_distance_counts_edits() {
+        let cases = [
+            ("", "", 0),
+            ("", "ping", 4),
+            ("ping", "", 4),
+            ("ping", "ping", 0),
+            ("ping", "pong", 1),
+            ("ping", "pings", 1),
+            ("ping", "pin", 1),
+            ("ping", "pnig", 1),
+            ("ping", "ipgn", 2),
+            ("kitten", "sitting", 3),
+            ("ca", "abc", 3),
+            ("café", "cafe", 1),
+            ("café", "cfaé", 1),
+            ("日本語", "日本", 1),
+            ("👍", "👎", 1),
+        ];
+
+        for (a, b, expected) in cases {
+            assert_eq!(edit_distance(a, b), expected, "{a:?} {b:?}");
+            assert_eq!(edit_distance(b, a), expected, "{b:?} {a:?}");
+        }
+    }
+
+    #[test]
+    fn suggestions_allow_one_edit_every_three_characters() {
+        let cases = [
+            ("", "a", Some(1)),
+            ("", "ab", None),
+            ("p", "q", Some(1)),
+            ("pi", "pin", Some(1)),
+            ("pi", "ping", None),
+            ("pnig", "ping", Some(1)),
+            ("pnig", "pong", Some(2)),
+            ("pnig", "prune", None),
+            ("PING", "ping", Some(0)),
+            ("Café", "CAFÉ", Some(0)),
+            ("baan", "ban", Some(1)),
+            ("lsit", "list", Some(1)),
+            ("settings", "setting", Some(1)),
+            ("settings", "sitting", Some(2)),
+            ("settings", "set", None),
+        ];
+
+        for (name, candidate, expected) in cases {
+            assert_eq!(
+                suggestion_distance(name, candidate),
+                expected,
+                "{name:?} {candidate:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn commands_are_recognized_by_name_alias_and_help() {
+        let mut registry = CommandRegistry::new(Box::new(String::from("!")), false, true);
+        registry.register_command(
+            CommandName::new("ping").alias("p").case_sensitive(false),
+            Noop(false),
+        );
+
+        assert!(registry.is_command("ping"));
+        assert!(registry.is_command("PING"));
+        assert!(registry.is_command("p"));
+        assert!(!registry.is_command("hello"));
+        assert!(!registry.is_command("help"));
+
+        registry.set_help_command(HelpCommand::new());
+        assert!(registry.is_command("help"));
+    }
+
+    #[test]
+    fn not_found_suggests_the_closest_visible_names() {
+        let mut registry = CommandRegistry::new(Box::new(String::from("!")), false, false);
+        registry.register_command(CommandName::new("ping").alias("p"), Noop(false));
+        registry.register_command(CommandName::new("pong"), Noop(false));
+        registry.register_command(CommandName::new("prune"), Noop(false));
+        registry.register_command(CommandName::new("pang"), Noop(true));
+        registry.set_help_command(HelpCommand::new());
+
+        let suggestions = |registry: &CommandRegistry, name: &str| match registry
+            .not_found("?", name.to_owned())
+        {
+            RSError::CommandNotFound { suggestions, .. } => suggestions,
+            err => panic!("unexpected error: {err:?}"),
+        };
+
+        assert_eq!(suggestions(&registry, "pnig"), ["?ping", "?pong"]);
+        assert_eq!(suggestions(&registry, "PINGG"), ["?ping", "?pong"]);
+        assert_eq!(suggestions(&registry, "hlep"), ["?help"]);
+        assert_eq!(suggestions(&registry, "pin"), ["?ping"]);
+        assert!(suggestions(&registry, "unknown").is_empty());
+
+        registry.register_command(CommandName::new("pung"), Noop(false));
+        registry.register_command(CommandName::new("ring"), Noop(false));
+        assert_eq!(suggestions(&registry, "pxng"), ["?ping", "?pong", "?pung"]);
     }
 }
@@ -1,4 +1,4 @@
-use anyhow::Result;
+use crate::error::{RSError, Result};
 
 use {
     std::sync::Arc,
@@ -30,39 +30,45 @@ pub struct Context {
     pub state: Arc<State>,
     /// A message.
     msg: Option<Arc<Message>>,
-    token: Arc<String>,
-    messenger: ActionMessenger,
+    messenger: Option<ActionMessenger>,
 }
 
 impl Context {
-    pub(crate) async fn new(
-        token: impl Into<String>,
+    pub(crate) fn new(
+        http_client: HttpClient,
         messenger: ActionMessenger,
-        msg: Option<Message>,
         state: Arc<State>,
     ) -> Self {
-        let token = token.into();
-        let http_client = HttpClient::new(&token).await;
-
         Self {
-            http_client,
-            #[cfg(feature = "cache")]
-            cache: Default::default(),
+            messenger: Some(messenger),
             #[cfg(feature = "state")]
-            state: state.clone(),
-            msg: msg.map(Arc::new),
-            token: Arc::new(token),
-            messenger,
+            state,
+            ..Self::from(http_client)
+        }
+    }
+
+    /// Returns a copy of the context for the specified message,
+    /// sharing the http client, the cache and the state.
+    pub(crate) fn with_message(&self, msg: Message) -> Self {
+        Self {
+            msg: Some(Arc::new(msg)),
+            ..self.clone()
         }
     }
 
-    /// Returns the given token.
-    pub(crate) fn token(&self) -> String {
-        self.token.as_ref().clone()
+    /// Whether the context is connected to the WebSocket API.
+    ///
+    /// Contexts created from a [`HttpClient`] can only use the REST API.
+    pub fn has_gateway(&self) -> bool {
+        self.messenger.is_some()
+    }
+
+    pub(crate) fn message(&self) -> Option<&Arc<Message>> {
+        self.msg.as_ref()
     }
 
-    pub(crate) fn messenger(&self) -> ActionMessenger {
-        self.messenger.clone()
+    fn messenger(&self) -> Result<&ActionMessenger> {
+        self.messenger.as_ref().ok_or(RSError::GatewayUnavailable)
     }
 
     /// Returns the current user.
@@ -77,7 +83,7 @@ impl Context {
 
     /// Tell other users that you have begin typing in a channel.
     pub async fn begin_typing(&self, channel_id: &ID) -> Result<()> {
-        self.messenger
+        self.messenger()?
             .send(ClientEvent::BeginTyping {
                 channel_id: channel_id.clone(),
             })
@@ -86,7 +92,7 @@ impl Context {
 
     /// Tell other users that you have stopped typing in a channel.
     pub async fn end_typing(&self, channel_id: &ID) -> Result<()> {
-        self.messenger
+        self.messenger()?
             .send(ClientEvent::EndTyping {
                 channel_id: channel_id.clone(),
             })
@@ -97,10 +103,10 @@ impl Context {
     ///
     /// If the client sent a heartbeat and did not receive it back, the function will sleep
     /// for `150` milliseconds and try again.
-    pub async fn latency(&self) -> Duration {
+    pub async fn latency(&self) -> Result<Duration> {
         loop {
-            match self.messenger.latency().await {
-                Some(latency) => return latency,
+            match self.messenger()?.latency().await? {
+                Some(latency) => return Ok(latency),
                 None => {
                     sleep(Duration::from_millis(150)).await;
                     continue;
@@ -111,7 +117,7 @@ impl Context {
 
     /// Close the WebSocket connection.
     pub async fn close(&self) -> Result<()> {
-        self.messenger.close().await
+        self.messenger()?.close().await
     }
 
     /// Fetch your direct messages, including any DM and group conversations.
@@ -128,3 +134,18 @@ impl Context {
         self.msg.clone().unwrap()
     }
 }
+
+impl From<HttpClient> for Context {
+    /// Creates a context that can only use the REST API, without a WebSocket connection.
+    fn from(http_client: HttpClient) -> Self {
+        Self {
+            http_client,
+            #[cfg(feature = "cache")]
+            cache: Default::default(),
+            #[cfg(feature = "state")]
+            state: Default::default(),
+            msg: None,
+            messenger: None,
+        }
+    }
+}
@@ -1,11 +1,10 @@
 use async_trait::async_trait;
 
-use crate::Context;
-use anyhow::Error;
+use crate::{error::RSError, Context};
 
-/// Trait for handling [`anyhow::Error`] errors
+/// Trait for handling [`RSError`] errors
 #[async_trait]
 pub trait RevoltErrorHandler: Send + Sync + 'static {
-    /// Handles the [`anyhow::Error`]
-    async fn handle(&self, ctx: &Context, error: Error);
+    /// Handles the [`RSError`]
+    async fn handle(&self, ctx: &Context, error: RSError);
 }
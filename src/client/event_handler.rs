@@ -1,4 +1,4 @@
-use anyhow::Result;
+use crate::error::Result;
 use async_trait::async_trait;
 
 use crate::{
@@ -9,6 +9,28 @@ use crate::{
 /// Define handlers for supported events.
 #[async_trait]
 pub trait RevoltEventHandler: Send + Sync + 'static {
+    /// The client has authenticated with the WebSocket API.
+    ///
+    /// Called after the first connection and after every reconnection.
+    async fn on_authenticated(&self, _ctx: &Context) -> Result<()> {
+        Ok(())
+    }
+
+    /// The connection to the WebSocket API was lost.
+    async fn on_disconnect(&self, _ctx: &Context, _reason: String) -> Result<()> {
+        Ok(())
+    }
+
+    /// The client is about to try to reconnect, `attempt` starts from `1`.
+    async fn on_reconnecting(&self, _ctx: &Context, _attempt: u32) -> Result<()> {
+        Ok(())
+    }
+
+    /// The client has reconnected and authenticated again after losing the connection.
+    async fn on_reconnected(&self, _ctx: &Context) -> Result<()> {
+        Ok(())
+    }
+
     /// Bot is ready.
     async fn on_ready(&self, _ctx: &Context, _data: ReadyEvent) -> Result<()> {
         Ok(())
@@ -29,6 +51,25 @@ pub trait RevoltEventHandler: Send + Sync + 'static {
         Ok(())
     }
 
+    /// A user has reacted to a message.
+    async fn on_message_react(&self, _ctx: &Context, _data: MessageReactEvent) -> Result<()> {
+        Ok(())
+    }
+
+    /// A user has removed their reaction to a message.
+    async fn on_message_unreact(&self, _ctx: &Context, _data: MessageUnreactEvent) -> Result<()> {
+        Ok(())
+    }
+
+    /// All the reactions with an emoji have been removed from a message.
+    async fn on_message_remove_reaction(
+        &self,
+        _ctx: &Context,
+        _data: MessageRemoveReactionEvent,
+    ) -> Result<()> {
+        Ok(())
+    }
+
     /// A channel has been created.
     async fn on_channel_create(&self, _ctx: &Context, _data: Channel) -> Result<()> {
         Ok(())
@@ -151,14 +192,16 @@ pub(crate) trait RevoltEventHandlerExt: RevoltEventHandler {
     async fn handle(&self, ctx: &Context, event: ServerEvent) -> Result<()> {
         match event {
             ServerEvent::Ready(data) => self.on_ready(ctx, data).await,
-            ServerEvent::Message(msg) => loop {
-                // Ignore messages that belong to current bot
-                if ctx.http_client.bot.id != msg.author_id {
-                    return self.on_message(ctx, msg).await;
-                }
-            },
+            // Ignore messages that belong to current bot
+            ServerEvent::Message(msg) if ctx.http_client.bot.id == msg.author_id => Ok(()),
+            ServerEvent::Message(msg) => self.on_message(ctx, msg).await,
             ServerEvent::MessageUpdate(data) => self.on_message_update(ctx, data).await,
             ServerEvent::MessageDelete(data) => self.on_message_delete(ctx, data).await,
+            ServerEvent::MessageReact(data) => self.on_message_react(ctx, data).await,
+            ServerEvent::MessageUnreact(data) => self.on_message_unreact(ctx, data).await,
+            ServerEvent::MessageRemoveReaction(data) => {
+                self.on_message_remove_reaction(ctx, data).await
+            }
             ServerEvent::ChannelCreate(channel) => self.on_channel_create(ctx, channel).await,
             ServerEvent::ChannelUpdate(data) => self.on_channel_update(ctx, data).await,
             ServerEvent::ChannelDelete(data) => self.on_channel_delete(ctx, data).await,
@@ -0,0 +1,265 @@
+use {
+    ahash::AHashMap,
+    async_trait::async_trait,
+    futures_util::future::{BoxFuture, FutureExt},
+    std::future::Future,
+};
+
+use crate::{
+    client::command::{find_command, RegisteredCommand},
+    error::{ArgumentError, RSError, Result},
+    Args, CommandName, Context, RevoltCommand,
+};
+
+type GroupCheck = Box<dyn Fn(Context) -> BoxFuture<'static, Result<bool>> + Send + Sync>;
+
+/// A command made of subcommands, like `!config prefix set ?`.
+///
+/// The first argument selects the subcommand by its [`CommandName`], which receives the
+/// remaining arguments.
+/// Groups can be nested, and the [`default_command`](Self::default_command) receives all the
+/// arguments when none of them selects a subcommand.
+#[derive(Default)]
+pub struct CommandGroup {
+    commands: AHashMap<String, RegisteredCommand>,
+    default_command: Option<String>,
+    check: Option<GroupCheck>,
+    description: Option<String>,
+    category: Option<String>,
+    hidden: bool,
+    usage: String,
+}
+
+impl CommandGroup {
+    /// Creates a new empty group.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a subcommand, which can be another group.
+    pub fn command(mut self, name: impl Into<CommandName>, command: impl RevoltCommand) -> Self {
+        let command = RegisteredCommand {
+            name: name.into(),
+            command: Box::new(command),
+        };
+
+        self.commands.insert(command.name.name.clone(), command);
+        self.update_usage();
+        self
+    }
+
+    /// Set the subcommand executed when the arguments do not select one,
+    /// by one of its names or aliases.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no subcommand matches the name, so the subcommand must be added first.
+    pub fn default_command(mut self, name: impl AsRef<str>) -> Self {
+        let name = name.as_ref();
+        let registered = find_command(&self.commands, name)
+            .unwrap_or_else(|| panic!("The default command {:?} is not a subcommand", name));
+
+        self.default_command = Some(registered.name.name.clone());
+        self.update_usage();
+        self
+    }
+
+    /// Set whether the caller can run the subcommands of the group.
+    pub fn check<F, Fut>(mut self, check: F) -> Self
+    where
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<bool>> + Send + 'static,
+    {
+        self.check = Some(Box::new(move |ctx| check(ctx).boxed()));
+        self
+    }
+
+    /// Set the description shown in the help.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the category used to group the commands in the help.
+    pub fn category(mut self, category: impl Into<String>) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    /// Set whether the group is hidden from the help.
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Sets the usage to the names of the subcommands, like `<get|set>`.
+    fn update_usage(&mut self) {
+        let mut names: Vec<_> = self.commands.keys().map(String::as_str).collect();
+        names.sort_unstable();
+
+        self.usage = match self.default_command {
+            Some(_) => format!("[{}]", names.join("|")),
+            None => format!("<{}>", names.join("|")),
+        };
+    }
+}
+
+#[async_trait]
+impl RevoltCommand for CommandGroup {
+    async fn execute(&self, ctx: &Context, mut args: Args) -> Result<()> {
+        let selected = args
+            .peek()
+            .and_then(|name| find_command(&self.commands, &name));
+
+        let RegisteredCommand { name, command } = match (selected, &self.default_command) {
+            (Some(selected), _) => {
+                args.next();
+                selected
+            }
+            // The default command is checked to be a subcommand when it is set.
+            (None, Some(name)) => &self.commands[name],
+            (None, None) => {
+                return Err(match args.next() {
+                    Some(value) => ArgumentError::Invalid {
+                        value,
+                        expected: "subcommand",
+                    },
+                    None => ArgumentError::Missing {
+                        expected: "subcommand",
+                    },
+                }
+                .into())
+            }
+        };
+
+        if !command.check(ctx).await? {
+            return Err(RSError::CheckFailed(name.name.clone()));
+        }
+
+        match command.execute(ctx, args).await {
+            Err(RSError::Argument { error, usage }) => Err(RSError::Argument {
+                error,
+                usage: Some(join_usage(&name.name, usage.as_deref().or(command.usage()))),
+            }),
+            result => result,
+        }
+    }
+
+    async fn check(&self, ctx: &Context) -> Result<bool> {
+        match self.check {
+            Some(ref check) => check(ctx.clone()).await,
+            None => Ok(true),
+        }
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    fn usage(&self) -> Option<&str> {
+        Some(&self.usage)
+    }
+
+    fn category(&self) -> Option<&str> {
+        self.category.as_deref()
+    }
+
+    fn hidden(&self) -> bool {
+        self.hidden
+    }
+
+    fn subcommands(&self) -> Vec<(&str, &dyn RevoltCommand)> {
+        let mut commands: Vec<_> = self
+            .commands
+            .iter()
+            .map(|(name, registered)| (name.as_str(), registered.command.as_ref()))
+            .collect();
+        commands.sort_unstable_by_key(|(name, _)| *name);
+
+        commands
+    }
+
+    fn subcommand(&self, name: &str) -> Option<(&str, &dyn RevoltCommand)> {
+        find_command(&self.commands, name)
+            .map(|registered| (registered.name.name.as_str(), registered.command.as_ref()))
+    }
+}
+
+/// Joins a command and its usage, like `!ban` and `<user> [reason]`.
+pub(crate) fn join_usage(command: &str, usage: Option<&str>) -> String {
+    match usage {
+        Some(usage) => format!("{} {}", command, usage),
+        None => command.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Noop;
+
+    #[async_trait]
+    impl RevoltCommand for Noop {
+        async fn execute(&self, _: &Context, _: Args) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn group() -> CommandGroup {
+        CommandGroup::new()
+            .command(
+                CommandName::new("ban").alias("b").case_sensitive(false),
+                Noop,
+            )
+            .command(CommandName::new("kick").alias("k"), Noop)
+            .command("mute", Noop)
+    }
+
+    #[test]
+    fn subcommands_match_names_and_aliases() {
+        let group = group();
+        let cases = [
+            ("ban", Some("ban")),
+            ("BAN", Some("ban")),
+            ("Ban", Some("ban")),
+            ("B", Some("ban")),
+            ("kick", Some("kick")),
+            ("k", Some("kick")),
+            ("Kick", None),
+            ("K", None),
+            ("mute", Some("mute")),
+            ("MUTE", None),
+            ("unban", None),
+            ("", None),
+        ];
+
+        for (name, expected) in cases {
+            let found = group.subcommand(name).map(|(name, _)| name);
+            assert_eq!(found, expected, "{name:?}");
+        }
+    }
+
+    #[test]
+    fn usage_lists_subcommand_names() {
+        assert_eq!(group().usage(), Some("<ban|kick|mute>"));
+        assert_eq!(
+            group().default_command("ban").usage(),
+            Some("[ban|kick|mute]")
+        );
+    }
+
+    #[test]
+    fn default_command_is_resolved_by_alias() {
+        for (alias, expected) in [("B", "ban"), ("k", "kick")] {
+            let default = group().default_command(alias).default_command;
+            assert_eq!(default.as_deref(), Some(expected));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a subcommand")]
+    fn default_command_must_be_a_subcommand() {
+        group().default_command("unban");
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+
+use crate::{builders::CreateEmbed, RevoltCommand};
+
+/// A built-in command listing the commands, or showing the detailed help of one command.
+///
+/// Register it with [`Client::set_help_command`](crate::Client::set_help_command).
+/// The commands that are hidden or whose [`check`](RevoltCommand::check) fails for the caller
+/// are not shown.
+#[derive(Debug, Clone)]
+pub struct HelpCommand {
+    pub(crate) name: String,
+    title: String,
+    colour: Option<String>,
+}
+
+impl Default for HelpCommand {
+    fn default() -> Self {
+        Self {
+            name: "help".into(),
+            title: "Help".into(),
+            colour: None,
+        }
+    }
+}
+
+impl HelpCommand {
+    /// Creates a new help command named `help`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the name of the command.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Set the title of the embeds.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Set the colour of the embeds, any valid CSS colour.
+    pub fn colour(mut self, colour: impl Into<String>) -> Self {
+        self.colour = Some(colour.into());
+        self
+    }
+
+    /// Renders the list of the commands, grouped by category.
+    pub(crate) fn list(
+        &self,
+        embed: CreateEmbed,
+        prefix: &str,
+        commands: &[(&str, &dyn RevoltCommand)],
+    ) -> CreateEmbed {
+        let mut categories: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for (name, command) in commands {
+            categories
+                .entry(command.category().unwrap_or("Commands"))
+                .or_default()
+                .push((name, command));
+        }
+
+        let mut description = String::new();
+        for (category, mut commands) in categories {
+            commands.sort_by_key(|(name, _)| *name);
+
+            description.push_str(&format!("**{}**\n", category));
+            for (name, command) in commands {
+                match command.description() {
+                    Some(about) => description.push_str(&format!("`{prefix}{name}` - {about}\n")),
+                    None => description.push_str(&format!("`{prefix}{name}`\n")),
+                }
+            }
+            description.push('\n');
+        }
+
+        description.push_str(&format!(
+            "Type `{}{} <command>` for more details.",
+            prefix, self.name
+        ));
+
+        self.embed(embed).description(description)
+    }
+
+    /// Renders the detailed help of a command, invoked with `invocation` like `!ban`.
+    pub(crate) fn command(
+        &self,
+        embed: CreateEmbed,
+        invocation: &str,
+        usage: &str,
+        aliases: &[String],
+        command: &dyn RevoltCommand,
+        subcommands: &[(&str, &dyn RevoltCommand)],
+    ) -> CreateEmbed {
+        let mut description = String::new();
+
+        if let Some(about) = command.description() {
+            description.push_str(&format!("{}\n\n", about));
+        }
+
+        description.push_str(&format!("**Usage**\n`{}`\n", usage));
+
+        if !aliases.is_empty() {
+            let aliases: Vec<_> = aliases.iter().map(|alias| format!("`{alias}`")).collect();
+            description.push_str(&format!("\n**Aliases**\n{}\n", aliases.join(", ")));
+        }
+
+        if !command.examples().is_empty() {
+            description.push_str("\n**Examples**\n");
+            for example in command.examples() {
+                description.push_str(&format!("`{} {}`\n", invocation, example));
+            }
+        }
+
+        if !subcommands.is_empty() {
+            description.push_str("\n**Subcommands**\n");
+            for (name, subcommand) in subcommands {
+                match subcommand.description() {
+                    Some(about) => {
+                        description.push_str(&format!("`{invocation} {name}` - {about}\n"))
+                    }
+                    None => description.push_str(&format!("`{invocation} {name}`\n")),
+                }
+            }
+        }
+
+        if let Some(category) = command.category() {
+            description.push_str(&format!("\n**Category**\n{}\n", category));
+        }
+
+        self.embed(embed).description(description)
+    }
+
+    fn embed(&self, embed: CreateEmbed) -> CreateEmbed {
+        let embed = embed.title(&self.title);
+
+        match self.colour {
+            Some(ref colour) => embed.color(colour),
+            None => embed,
+        }
+    }
+}
@@ -2,26 +2,38 @@ use self::command_registry::CommandRegistry;
 pub(crate) use action::*;
 use tokio::sync::RwLock;
 
+pub use crate::websocket::{ConnectionState, WireFormat};
 pub use context::Context;
-pub use {command::*, error_handler::*, event_handler::*};
+pub use {
+    args::*, builder::*, command::*, error_handler::*, event_handler::*, group::*, help::*,
+    prefix::*, reconnect::*,
+};
 
 mod action;
+mod args;
+mod builder;
 mod command;
 mod command_registry;
 mod context;
 mod error_handler;
 mod event_handler;
+mod group;
+mod help;
+mod prefix;
+mod reconnect;
 
 // Client struct
 
 use {
     futures_util::{select, FutureExt},
     std::sync::Arc,
+    tokio::time::{interval, sleep, MissedTickBehavior},
     tracing::{error, info, warn},
 };
 
 use crate::{
-    error::RSError,
+    error::{AuthenticationError, RSError},
+    http::RetryPolicy,
     models::{
         events::{ClientEvent, ServerEvent},
         Content,
@@ -30,10 +42,19 @@ use crate::{
     websocket::WebSocketClient,
 };
 
-use anyhow::Result;
+use crate::error::Result;
 
 #[cfg(feature = "cache")]
 use crate::cache::Cache;
+#[cfg(feature = "reaction_roles")]
+use {
+    crate::reaction_roles::ReactionRoles,
+    tokio::sync::mpsc::{unbounded_channel, UnboundedSender},
+};
+
+/// Sends the events to handle to the reaction roles, with the context to handle them with.
+#[cfg(feature = "reaction_roles")]
+type ReactionRoleQueue = UnboundedSender<(Context, ServerEvent)>;
 
 /// API wrapper to interact with Revolt.
 pub struct Client<T: RevoltEventHandler> {
@@ -42,56 +63,103 @@ pub struct Client<T: RevoltEventHandler> {
     action_rx: ActionRx,
     partial_context: Context,
     command_registry: Arc<RwLock<CommandRegistry>>,
-    state: Arc<State>,
+    reconnect_policy: ReconnectPolicy,
+    connection_state: ConnectionState,
+    authenticated_once: bool,
+    #[cfg(feature = "reaction_roles")]
+    reaction_roles: Option<ReactionRoleQueue>,
 }
 
 impl<T: RevoltEventHandler> Client<T> {
-    /// Create a new client and connect to the server.
+    /// Create a new client and connect to the official Revolt instance.
+    ///
+    /// Use [`Client::builder`] for more options.
     pub async fn new(
         token: impl Into<String>,
         prefix: impl Into<String>,
         event_handler: T,
         state: Option<Arc<State>>,
     ) -> Result<Self> {
-        let ws_client = WebSocketClient::connect().await?;
-        let (messenger, action_rx) = ActionMessenger::new();
-        let partial_context = Context::new(
-            token,
-            messenger,
-            None,
-            state.unwrap_or(Arc::new(State::default())),
-        )
-        .await;
-
-        Ok(Self {
-            event_handler: Arc::new(event_handler),
-            command_registry: Arc::new(RwLock::new(CommandRegistry::new(prefix.into()))),
-            ws_client,
-            action_rx,
-            partial_context,
-            state: Arc::new(State::default()), // state really needed in client struct?
-        })
+        let mut builder = ClientBuilder::new(token, event_handler).prefix(prefix);
+
+        if let Some(state) = state {
+            builder = builder.state(state);
+        }
+
+        builder.build().await
+    }
+
+    /// Creates a new [`ClientBuilder`].
+    pub fn builder(token: impl Into<String>, event_handler: T) -> ClientBuilder<T> {
+        ClientBuilder::new(token, event_handler)
     }
 
     /// Start listening for server events.
+    ///
+    /// If the connection is lost, the client reconnects according to its [`ReconnectPolicy`].
+    /// Returns once the connection is closed with [`Context::close`], or with an error
+    /// if the client could not reconnect.
     #[allow(clippy::missing_panics_doc)]
     pub async fn listen(&mut self) -> Result<()> {
         self.authenticate().await?;
 
         info!(target: "Client", "Client authenticated successfully. Starting listening for events");
+        self.sync_connection_state().await;
 
         loop {
-            if let Err(err) = self.ws_client.check_heartbeat().await {
-                warn!(target: "Client", "Err heartbeating: {}", err);
+            let result = self.run().await;
+            self.sync_connection_state().await;
+
+            match result {
+                Ok(()) => {
+                    info!(target: "Client", "Connection closed");
+                    return Ok(());
+                }
+                Err(err) => {
+                    warn!(target: "Client", "Connection lost: {}", err);
+                    self.reconnect().await?;
+
+                    info!(target: "Client", "Client reconnected successfully");
+                    self.sync_connection_state().await;
+                }
             }
+        }
+    }
+
+    /// Set the [`ReconnectPolicy`] used when the connection is lost.
+    pub fn set_reconnect_policy(&mut self, policy: ReconnectPolicy) {
+        self.reconnect_policy = policy;
+    }
 
+    /// Set the [`RetryPolicy`] used when a request to the REST API fails.
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.partial_context.http_client.retry_policy = policy;
+    }
+
+    /// Handles events and actions until the connection is closed.
+    ///
+    /// Returns `Ok` if the connection was closed by the client and an error if it was lost.
+    async fn run(&mut self) -> Result<()> {
+        let mut heartbeat = interval(self.ws_client.heartbeat_dur());
+        heartbeat.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
             select! {
-                event = self.ws_client.accept().fuse() => {
-                    if let Some(event) = event {
-                        self.handle_event(event).await;
-                    } else {
-                        info!(target: "Client", "Connection closed");
-                        return Ok(());
+                _ = heartbeat.tick().fuse() => self.ws_client.check_heartbeat().await?,
+                event = self.ws_client.accept().fuse() => match event {
+                    Some(Err(err)) if is_connection_error(&err) => {
+                        if *self.ws_client.state() == ConnectionState::Closed {
+                            return Ok(());
+                        }
+
+                        return Err(err);
+                    }
+                    Some(event) => self.handle_event(event).await,
+                    None if *self.ws_client.state() == ConnectionState::Closed => return Ok(()),
+                    None => {
+                        return Err(RSError::ConnectionLost(
+                            "The server closed the connection".into(),
+                        ))
                     }
                 },
                 action = self.action_rx.recv().fuse() => self.handle_action(action.unwrap()).await,
@@ -99,10 +167,49 @@ impl<T: RevoltEventHandler> Client<T> {
         }
     }
 
+    /// Opens a new connection and authenticates again, waiting between attempts.
+    async fn reconnect(&mut self) -> Result<()> {
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+
+            if !self.reconnect_policy.allows(attempt) {
+                return Err(RSError::ConnectionLost(format!(
+                    "Could not reconnect after {} attempts",
+                    attempt - 1
+                )));
+            }
+
+            self.ws_client.begin_reconnect(attempt);
+            self.sync_connection_state().await;
+
+            let delay = self.reconnect_policy.delay(attempt);
+            info!(target: "Client", "Reconnecting in {:?} (attempt {})", delay, attempt);
+            sleep(delay).await;
+
+            match self.ws_client.reconnect().await {
+                Ok(()) => {}
+                Err(err) => {
+                    warn!(target: "Client", "Err reconnecting: {}", err);
+                    continue;
+                }
+            }
+
+            match self.authenticate().await {
+                Ok(()) => return Ok(()),
+                Err(err @ RSError::Authentication(AuthenticationError::InvalidSession)) => {
+                    return Err(err)
+                }
+                Err(err) => warn!(target: "Client", "Err authenticating: {}", err),
+            }
+        }
+    }
+
     async fn authenticate(&mut self) -> Result<()> {
         self.ws_client
             .send(ClientEvent::Authenticate {
-                token: self.partial_context.token(),
+                token: self.partial_context.http_client.token().to_owned(),
             })
             .await?;
 
@@ -116,19 +223,70 @@ impl<T: RevoltEventHandler> Client<T> {
             event => Err(RSError::Unknown(format!(
                 "Unexpected event received while authenticating: {:?}",
                 event
-            ))
-            .into()),
+            ))),
+        }
+    }
+
+    /// Calls the lifecycle handlers if the connection state changed since the last call.
+    async fn sync_connection_state(&mut self) {
+        let state = self.ws_client.state().clone();
+
+        if state == self.connection_state {
+            return;
+        }
+
+        self.connection_state = state.clone();
+
+        let ctx = &self.partial_context;
+        let result = match state {
+            ConnectionState::Authenticated => {
+                let reconnected = self.authenticated_once;
+                self.authenticated_once = true;
+
+                match self.event_handler.on_authenticated(ctx).await {
+                    Ok(()) if reconnected => self.event_handler.on_reconnected(ctx).await,
+                    result => result,
+                }
+            }
+            ConnectionState::Disconnected { reason } => {
+                self.event_handler.on_disconnect(ctx, reason).await
+            }
+            ConnectionState::Reconnecting { attempt } => {
+                self.event_handler.on_reconnecting(ctx, attempt).await
+            }
+            ConnectionState::Connected | ConnectionState::Closed => Ok(()),
+        };
+
+        if let Err(err) = result {
+            self.command_registry
+                .read()
+                .await
+                .handle_error(ctx, err)
+                .await
+                .ok();
         }
     }
 
     async fn handle_event(&self, event: Result<ServerEvent>) {
         match event {
             Ok(event) => {
+                #[cfg(feature = "reaction_roles")]
+                if let Some(ref reaction_roles) = self.reaction_roles {
+                    if matches!(
+                        event,
+                        ServerEvent::Ready(_)
+                            | ServerEvent::MessageReact(_)
+                            | ServerEvent::MessageUnreact(_)
+                    ) {
+                        reaction_roles
+                            .send((self.partial_context.clone(), event.clone()))
+                            .ok();
+                    }
+                }
+
                 let event_handler = self.event_handler.clone();
                 let command_registry = self.command_registry.clone();
                 let partial_ctx = self.partial_context.clone();
-                let command_prefix = self.command_registry.read().await.prefix.clone();
-                let state = self.state.clone();
 
                 tokio::spawn(async move {
                     #[cfg(feature = "cache")]
@@ -136,24 +294,42 @@ impl<T: RevoltEventHandler> Client<T> {
 
                     if let ServerEvent::Message(ref msg) = event {
                         if let Content::Text(content) = &msg.content {
-                            let words: Vec<&str> = content.split_whitespace().collect();
-                            if words[0].starts_with(&command_prefix) {
-                                let ctx = Context::new(
-                                    partial_ctx.token(),
-                                    partial_ctx.messenger(),
-                                    Some(msg.clone()),
-                                    state.clone(),
-                                )
+                            let ctx = partial_ctx.with_message(msg.clone());
+                            let command = command_registry
+                                .read()
+                                .await
+                                .strip_prefix(&ctx, msg, content)
                                 .await;
-                                if let Err(err) = command_registry
-                                    .read()
-                                    .await
-                                    .execute_command(
-                                        words[0].strip_prefix(&command_prefix).unwrap(),
-                                        &ctx,
-                                    )
-                                    .await
-                                {
+
+                            match command {
+                                Ok(Some((prefix, args))) => {
+                                    if let Err(err) = command_registry
+                                        .read()
+                                        .await
+                                        .execute_command(&ctx, &prefix, args)
+                                        .await
+                                    {
+                                        command_registry
+                                            .read()
+                                            .await
+                                            .handle_error(&ctx, err)
+                                            .await
+                                            .ok();
+                                    }
+                                }
+                                Ok(None) => {
+                                    if let Err(err) =
+                                        event_handler.handle(&partial_ctx, event).await
+                                    {
+                                        command_registry
+                                            .read()
+                                            .await
+                                            .handle_error(&partial_ctx, err)
+                                            .await
+                                            .ok();
+                                    }
+                                }
+                                Err(err) => {
                                     command_registry
                                         .read()
                                         .await
@@ -161,14 +337,6 @@ impl<T: RevoltEventHandler> Client<T> {
                                         .await
                                         .ok();
                                 }
-                            } else if let Err(err) = event_handler.handle(&partial_ctx, event).await
-                            {
-                                command_registry
-                                    .read()
-                                    .await
-                                    .handle_error(&partial_ctx, err)
-                                    .await
-                                    .ok();
                             }
                         }
                     } else if let Err(err) = event_handler.handle(&partial_ctx, event).await {
@@ -194,9 +362,11 @@ impl<T: RevoltEventHandler> Client<T> {
     }
 
     /// Registers a new [`RevoltCommand`] and its name to the internal command registry
+    ///
+    /// The name can be a string, or a [`CommandName`] with aliases and case insensitive matching.
     pub async fn register_command(
         &mut self,
-        command_name: impl Into<String>,
+        command_name: impl Into<CommandName>,
         command: impl RevoltCommand,
     ) {
         self.command_registry
@@ -205,6 +375,22 @@ impl<T: RevoltEventHandler> Client<T> {
             .register_command(command_name.into(), command);
     }
 
+    /// Sets the specified [`PrefixResolver`] in the internal command registry
+    pub async fn set_prefix_resolver(&mut self, prefix_resolver: impl PrefixResolver) {
+        self.command_registry
+            .write()
+            .await
+            .set_prefix_resolver(prefix_resolver);
+    }
+
+    /// Sets the specified [`HelpCommand`] in the internal command registry
+    pub async fn set_help_command(&mut self, help_command: HelpCommand) {
+        self.command_registry
+            .write()
+            .await
+            .set_help_command(help_command);
+    }
+
     /// Sets the specified [`RevoltErrorHandler`] in the internal command registry
     pub async fn set_error_handler(&mut self, error_handler: impl RevoltErrorHandler) {
         self.command_registry
@@ -213,3 +399,33 @@ impl<T: RevoltEventHandler> Client<T> {
             .set_error_handler(error_handler);
     }
 }
+
+/// Handles the events sent to the returned queue one at a time, in the order they were sent,
+/// so that a reaction and its removal update the roles in the same order.
+#[cfg(feature = "reaction_roles")]
+fn spawn_reaction_roles(
+    reaction_roles: ReactionRoles,
+    command_registry: Arc<RwLock<CommandRegistry>>,
+) -> ReactionRoleQueue {
+    let (tx, mut rx) = unbounded_channel::<(Context, ServerEvent)>();
+
+    tokio::spawn(async move {
+        while let Some((ctx, event)) = rx.recv().await {
+            if let Err(err) = reaction_roles.handle(&ctx, &event).await {
+                command_registry
+                    .read()
+                    .await
+                    .handle_error(&ctx, err)
+                    .await
+                    .ok();
+            }
+        }
+    });
+
+    tx
+}
+
+/// Whether the error means that the WebSocket connection is no longer usable.
+fn is_connection_error(err: &RSError) -> bool {
+    matches!(err, RSError::Ws(_) | RSError::ConnectionLost(_))
+}
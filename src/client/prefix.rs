@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+
+use crate::{error::Result, models::Message, Context};
+
+/// Resolves the command prefixes allowed in a message.
+///
+/// Called for every message, with the [`Context`] to read the [`State`](crate::state::State)
+/// or a database, for example to use a different prefix in each server.
+#[async_trait]
+pub trait PrefixResolver: Send + Sync + 'static {
+    /// Returns the command prefixes allowed in the message, the longest matching one is used.
+    async fn prefixes(&self, ctx: &Context, msg: &Message) -> Result<Vec<String>>;
+}
+
+/// A single prefix used everywhere.
+#[async_trait]
+impl PrefixResolver for String {
+    async fn prefixes(&self, _: &Context, _: &Message) -> Result<Vec<String>> {
+        Ok(vec![self.clone()])
+    }
+}
+
+/// Multiple prefixes used everywhere.
+#[async_trait]
+impl PrefixResolver for Vec<String> {
+    async fn prefixes(&self, _: &Context, _: &Message) -> Result<Vec<String>> {
+        Ok(self.clone())
+    }
+}
@@ -0,0 +1,70 @@
+use {crate::backoff::Backoff, std::time::Duration};
+
+/// Policy used by the [`Client`](crate::Client) to reconnect after the WebSocket connection is lost.
+///
+/// The delay before each attempt grows exponentially from [`min_backoff`](Self::min_backoff)
+/// up to [`max_backoff`](Self::max_backoff), with a random jitter applied to it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReconnectPolicy {
+    /// Maximum number of consecutive reconnection attempts, `None` means unlimited.
+    pub max_attempts: Option<u32>,
+    /// Delay before the first reconnection attempt.
+    pub min_backoff: Duration,
+    /// Upper bound of the delay between reconnection attempts.
+    pub max_backoff: Duration,
+}
+
+impl ReconnectPolicy {
+    /// Creates a new [`ReconnectPolicy`].
+    pub fn new(max_attempts: Option<u32>, min_backoff: Duration, max_backoff: Duration) -> Self {
+        Self {
+            max_attempts,
+            min_backoff,
+            max_backoff,
+        }
+    }
+
+    /// A policy that never reconnects.
+    pub fn disabled() -> Self {
+        Self {
+            max_attempts: Some(0),
+            ..Default::default()
+        }
+    }
+
+    /// Set the maximum number of consecutive reconnection attempts.
+    pub fn max_attempts(mut self, max_attempts: Option<u32>) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Set the lower and upper bounds of the delay between reconnection attempts.
+    pub fn backoff(mut self, min: Duration, max: Duration) -> Self {
+        self.min_backoff = min;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Whether the specified attempt (starting from `1`) is allowed by the policy.
+    pub(crate) fn allows(&self, attempt: u32) -> bool {
+        match self.max_attempts {
+            Some(max) => attempt <= max,
+            None => true,
+        }
+    }
+
+    /// Returns the delay to wait before the specified attempt (starting from `1`).
+    pub(crate) fn delay(&self, attempt: u32) -> Duration {
+        Backoff::new(self.min_backoff, self.max_backoff).delay(attempt)
+    }
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: None,
+            min_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+}
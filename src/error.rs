@@ -1,25 +1,99 @@
 //! Module for [derive@Error] and [Result] types.
 
 use {
-    crate::models::RevoltPermission, reqwest::Error as HttpError, serde::Deserialize,
-    thiserror::Error, tokio_tungstenite::tungstenite::Error as WsError,
+    crate::models::Permissions,
+    reqwest::{Error as HttpError, StatusCode},
+    serde::Deserialize,
+    std::time::Duration,
+    thiserror::Error,
+    tokio_tungstenite::tungstenite::Error as WsError,
 };
 
+/// Result type used across [resvolt](crate).
+pub type Result<T, E = RSError> = std::result::Result<T, E>;
+
 /// Errors that can happen when using [resvolt](crate).
 #[derive(Error, Debug)]
 pub enum RSError {
     /// Command not found in the client's internal command registry
-    #[error("Command not found: {0}")]
-    CommandNotFound(String),
+    #[error("Command not found: {name}")]
+    CommandNotFound {
+        /// The name of the command.
+        name: String,
+        /// The closest registered commands with the prefix, like `!purge`, closest first.
+        suggestions: Vec<String>,
+    },
+    /// The check of a command did not pass for the caller.
+    #[error("Check failed for command: {0}")]
+    CheckFailed(String),
+    /// The arguments of a command could not be parsed.
+    #[error("Invalid arguments: {error}")]
+    Argument {
+        /// Why the arguments could not be parsed.
+        error: ArgumentError,
+        /// How to use the command, like `!ban <user> [reason]`.
+        usage: Option<String>,
+    },
+    /// The API rejected a request.
+    #[error("API error on {route} ({status}): {error}")]
+    Api {
+        /// The request method and path.
+        route: String,
+        /// The response status.
+        status: StatusCode,
+        /// The error returned by the API.
+        error: APIError,
+    },
+    /// The API rate limited a request.
+    #[error("Rate limited on {route}, retry after {retry_after:?}")]
+    RateLimited {
+        /// The request method and path.
+        route: String,
+        /// How long to wait before retrying the request.
+        retry_after: Duration,
+    },
+    /// The API returned an unsuccessful response that is not an [`APIError`].
+    #[error("Unexpected response on {route} ({status}): {body}")]
+    UnexpectedResponse {
+        /// The request method and path.
+        route: String,
+        /// The response status.
+        status: StatusCode,
+        /// The response body.
+        body: String,
+    },
     /// Http requests error.
     #[error("HTTP error: {0}")]
     Http(#[from] HttpError),
+    /// An attachment is larger than the allowed size.
+    #[error("Attachment too large: {size} bytes, the limit is {max_size} bytes")]
+    AttachmentTooLarge {
+        /// The size of the attachment, or the number of bytes received so far.
+        size: usize,
+        /// The allowed size.
+        max_size: usize,
+    },
+    /// JSON serialization or deserialization error.
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    /// I/O error, for example when reading a file to upload.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
     /// WebSocket error.
     #[error("WebSocket error: {0}")]
-    Ws(#[from] WsError),
+    Ws(Box<WsError>),
+    /// The WebSocket connection was lost.
+    #[error("Connection lost: {0}")]
+    ConnectionLost(String),
+    /// The operation requires a WebSocket connection, which the [`Context`](crate::Context) does not have.
+    #[error("No WebSocket connection to the gateway")]
+    GatewayUnavailable,
     /// Could not authenticate due to an error.
     #[error("Authentication error: {0}")]
     Authentication(#[from] AuthenticationError),
+    /// Invalid client configuration.
+    #[error("Invalid configuration: {0}")]
+    Config(String),
     /// Unknown or unexpected error.
     #[error("Unknown error: {0}")]
     Unknown(String),
@@ -28,6 +102,86 @@ pub enum RSError {
     ErrorHandlerNotSet,
 }
 
+impl From<WsError> for RSError {
+    fn from(err: WsError) -> Self {
+        Self::Ws(Box::new(err))
+    }
+}
+
+impl From<ArgumentError> for RSError {
+    fn from(error: ArgumentError) -> Self {
+        Self::Argument { error, usage: None }
+    }
+}
+
+impl RSError {
+    /// Returns the [`APIError`] if the API rejected the request.
+    pub fn api_error(&self) -> Option<&APIError> {
+        match self {
+            Self::Api { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Returns the response status if the request failed with an unsuccessful response.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::Api { status, .. } | Self::UnexpectedResponse { status, .. } => Some(*status),
+            Self::RateLimited { .. } => Some(StatusCode::TOO_MANY_REQUESTS),
+            Self::Http(err) => err.status(),
+            _ => None,
+        }
+    }
+
+    /// Returns how to use the command if its arguments could not be parsed.
+    pub fn usage(&self) -> Option<&str> {
+        match self {
+            Self::Argument { usage, .. } => usage.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Returns the route of the request if the error was returned by the API.
+    pub fn route(&self) -> Option<&str> {
+        match self {
+            Self::Api { route, .. }
+            | Self::RateLimited { route, .. }
+            | Self::UnexpectedResponse { route, .. } => Some(route),
+            _ => None,
+        }
+    }
+}
+
+/// Command argument parsing error.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ArgumentError {
+    /// An argument is missing.
+    #[error("Missing {expected}")]
+    Missing {
+        /// What the argument should have been, like `integer` or `user`.
+        expected: &'static str,
+    },
+    /// An argument could not be parsed.
+    #[error("Invalid {expected}: {value}")]
+    Invalid {
+        /// The argument.
+        value: String,
+        /// What the argument should have been, like `integer` or `user`.
+        expected: &'static str,
+    },
+    /// An argument refers to something that does not exist, like an unknown user.
+    #[error("Unknown {expected}: {value}")]
+    NotFound {
+        /// The argument.
+        value: String,
+        /// What the argument should have been, like `integer` or `user`.
+        expected: &'static str,
+    },
+    /// An argument was given but the command does not expect it.
+    #[error("Unexpected argument: {0}")]
+    Unexpected(String),
+}
+
 /// Authentication error.
 #[derive(Error, Debug, Deserialize, Clone, Copy, PartialEq)]
 #[error("{self:?}")]
@@ -98,7 +252,7 @@ pub enum APIError {
     BotIsPrivate,
     CannotReportYourself,
     MissingPermission {
-        permission: RevoltPermission,
+        permission: Permissions,
     },
     MissingUserPermission {
         permission: String,
@@ -0,0 +1,96 @@
+use {
+    bytes::Bytes,
+    futures_util::TryStream,
+    reqwest::{
+        multipart::{Form, Part},
+        Body, Method, Response,
+    },
+    serde::Deserialize,
+    std::error::Error as StdError,
+};
+
+use super::HttpClient;
+use crate::{
+    builders::{AttachmentData, CreateAttachment},
+    error::{RSError, Result},
+    models::{AttachmentTag, ID},
+};
+
+/// Body of a successful upload.
+#[derive(Debug, Deserialize)]
+struct Uploaded {
+    id: ID,
+}
+
+impl HttpClient {
+    /// Upload a file to the file server (Autumn) and return the id of the attachment.
+    ///
+    /// The id can be used in the builders, for example as a message attachment or an avatar.
+    pub async fn upload(&self, tag: AttachmentTag, attachment: CreateAttachment) -> Result<ID> {
+        let data = match attachment.data {
+            AttachmentData::Bytes(data) => Bytes::from(data),
+            AttachmentData::Path(path) => Bytes::from(tokio::fs::read(path).await?),
+        };
+
+        self.upload_part(tag, attachment.filename, attachment.content_type, || {
+            Ok(Body::from(data.clone()))
+        })
+        .await
+    }
+
+    /// Upload a stream of bytes to the file server (Autumn) and return the id of the attachment.
+    ///
+    /// The stream can only be sent once, so the upload is not retried if it fails.
+    pub async fn upload_stream<S>(
+        &self,
+        tag: AttachmentTag,
+        filename: impl Into<String>,
+        stream: S,
+    ) -> Result<ID>
+    where
+        S: TryStream + Send + Sync + 'static,
+        S::Error: Into<Box<dyn StdError + Send + Sync>>,
+        Bytes: From<S::Ok>,
+    {
+        let mut body = Some(Body::wrap_stream(stream));
+
+        self.upload_part(tag, filename.into(), None, || {
+            body.take()
+                .ok_or_else(|| RSError::Unknown("The stream was already uploaded".into()))
+        })
+        .await
+    }
+
+    /// Make a `GET` request to a file of the file server (Autumn).
+    ///
+    /// The rate limit bucket is looked up with `path`, the path of the file without its name.
+    pub(crate) async fn download(&self, url: &str, path: &str) -> Result<Response> {
+        self.send_to(Method::GET, url, path, Ok).await
+    }
+
+    /// Upload the body returned by `body` as a file, `body` is called for every attempt.
+    async fn upload_part(
+        &self,
+        tag: AttachmentTag,
+        filename: String,
+        content_type: Option<String>,
+        mut body: impl FnMut() -> Result<Body>,
+    ) -> Result<ID> {
+        let url = format!("{}/{}", self.endpoints.autumn, tag.as_str());
+
+        let response = self
+            .send_to(Method::POST, &url, tag.as_str(), |request| {
+                let mut part = Part::stream(body()?).file_name(filename.clone());
+
+                if let Some(content_type) = &content_type {
+                    part = part.mime_str(content_type)?;
+                }
+
+                Ok(request.multipart(Form::new().part("file", part)))
+            })
+            .await?;
+        let uploaded: Uploaded = response.json().await?;
+
+        Ok(uploaded.id)
+    }
+}
@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use super::{Endpoints, HttpClient, RetryPolicy};
+use crate::error::Result;
+
+/// Builder for a [`HttpClient`].
+///
+/// Allows using the REST API of a self-hosted Revolt instance by changing the [`Endpoints`].
+#[derive(Debug, Clone)]
+pub struct HttpClientBuilder {
+    token: String,
+    endpoints: Endpoints,
+    https_only: bool,
+    retry_policy: RetryPolicy,
+}
+
+impl HttpClientBuilder {
+    /// Creates a new builder with the official Revolt [`Endpoints`].
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+            endpoints: Endpoints::default(),
+            https_only: true,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Set the [`Endpoints`] of the Revolt instance.
+    pub fn endpoints(mut self, endpoints: Endpoints) -> Self {
+        self.endpoints = endpoints;
+        self
+    }
+
+    /// Set whether only secure connections (`https`) are allowed, `true` by default.
+    pub fn https_only(mut self, https_only: bool) -> Self {
+        self.https_only = https_only;
+        self
+    }
+
+    /// Set the [`RetryPolicy`] used when a request fails.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Create the client and fetch the current user.
+    ///
+    /// Fails if the token is invalid or the current user cannot be fetched.
+    pub async fn build(self) -> Result<HttpClient> {
+        HttpClient::connect(
+            self.token,
+            Arc::new(self.endpoints),
+            self.https_only,
+            self.retry_policy,
+        )
+        .await
+    }
+}
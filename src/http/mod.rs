@@ -0,0 +1,396 @@
+//! A module for the http client that makes requests to the Revolt REST API.
+
+use {
+    crate::models::{NodeInfo, User},
+    ratelimit::RateLimiter,
+    reqwest::{
+        header::{HeaderMap, HeaderValue},
+        Client, Method, RequestBuilder, Response, StatusCode,
+    },
+    serde::{de::DeserializeOwned, ser::Serialize, Deserialize},
+    std::{sync::Arc, time::Duration},
+    tokio::time::sleep,
+    tracing::warn,
+};
+
+use crate::error::{APIError, AuthenticationError, RSError, Result};
+
+pub use {builder::HttpClientBuilder, retry::RetryPolicy};
+
+mod autumn;
+mod builder;
+mod ratelimit;
+mod retry;
+
+/// The url of the [Revolt REST API](https://developers.revolt.chat/api/).
+const DELTA_API: &str = "https://api.revolt.chat";
+/// The url of the Revolt WebSocket API.
+const BONFIRE_API: &str = "wss://ws.revolt.chat";
+/// The url of the Revolt file server.
+const AUTUMN_API: &str = "https://autumn.revolt.chat";
+
+/// The urls of the Revolt services to connect to.
+///
+/// Defaults to the official [revolt.chat](https://revolt.chat) instance,
+/// use [`Endpoints::discover`] to find the urls of a self-hosted instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoints {
+    /// The url of the REST API (Delta).
+    pub api: String,
+    /// The url of the WebSocket API (Bonfire).
+    pub ws: String,
+    /// The url of the file server (Autumn).
+    pub autumn: String,
+}
+
+impl Endpoints {
+    /// Creates new [`Endpoints`].
+    pub fn new(api: impl Into<String>, ws: impl Into<String>, autumn: impl Into<String>) -> Self {
+        Self {
+            api: trim_url(api),
+            ws: trim_url(ws),
+            autumn: trim_url(autumn),
+        }
+    }
+
+    /// Discover the urls of a Revolt instance from the root of its REST API.
+    ///
+    /// The url of the file server is empty if the instance does not have it enabled.
+    pub async fn discover(api: impl Into<String>, https_only: bool) -> Result<Self> {
+        let api = trim_url(api);
+        let node_info: NodeInfo = Client::builder()
+            .https_only(https_only)
+            .build()?
+            .get(&api)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let autumn = match node_info.features.autumn {
+            autumn if autumn.enabled => autumn.url,
+            _ => String::new(),
+        };
+
+        Ok(Self::new(api, node_info.ws, autumn))
+    }
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self::new(DELTA_API, BONFIRE_API, AUTUMN_API)
+    }
+}
+
+fn trim_url(url: impl Into<String>) -> String {
+    url.into().trim_end_matches('/').to_owned()
+}
+
+/// A struct to execute requests to the [Revolt REST API](https://developers.revolt.chat/api/).
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    client: Client,
+    token: Arc<String>,
+    rate_limiter: Arc<RateLimiter>,
+    pub(crate) retry_policy: RetryPolicy,
+    pub(crate) endpoints: Arc<Endpoints>,
+    pub(crate) bot: User,
+}
+
+impl HttpClient {
+    /// Creates a new [`HttpClient`] for the official Revolt instance and fetches the current user.
+    ///
+    /// The client can be used without a WebSocket connection, for example in scripts
+    /// that only send messages. Use [`HttpClient::builder`] for more options.
+    ///
+    /// Fails if the token is invalid or the current user cannot be fetched.
+    pub async fn new(token: impl Into<String>) -> Result<Self> {
+        Self::builder(token).build().await
+    }
+
+    /// Creates a new [`HttpClientBuilder`].
+    pub fn builder(token: impl Into<String>) -> HttpClientBuilder {
+        HttpClientBuilder::new(token)
+    }
+
+    pub(crate) async fn connect(
+        token: String,
+        endpoints: Arc<Endpoints>,
+        https_only: bool,
+        retry_policy: RetryPolicy,
+    ) -> Result<Self> {
+        let mut bot_token = HeaderValue::from_str(&token)
+            .map_err(|_| RSError::Authentication(AuthenticationError::InvalidSession))?;
+        bot_token.set_sensitive(true);
+        let mut headers = HeaderMap::new();
+        headers.insert("X-BOT-TOKEN", bot_token);
+
+        let client = Client::builder()
+            .default_headers(headers)
+            .https_only(https_only)
+            .build()?;
+
+        let response = client
+            .get(format!("{}/users/@me", endpoints.api))
+            .send()
+            .await?;
+        let retry_after = retry::retry_after(response.headers());
+        let bot: User = Self::check_response(response, "GET /users/@me".into(), retry_after)
+            .await?
+            .json()
+            .await?;
+
+        Ok(Self {
+            client,
+            token: Arc::new(token),
+            bot,
+            endpoints,
+            rate_limiter: Default::default(),
+            retry_policy,
+        })
+    }
+
+    /// Returns the current user, fetched when the client was created.
+    pub fn bot(&self) -> &User {
+        &self.bot
+    }
+
+    /// Returns the urls of the Revolt services used by the client.
+    pub fn endpoints(&self) -> &Endpoints {
+        &self.endpoints
+    }
+
+    /// Returns the [`RetryPolicy`] used when a request fails.
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy
+    }
+
+    /// Set the [`RetryPolicy`] used when a request fails.
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.retry_policy = policy;
+    }
+
+    /// Returns the token used to authenticate.
+    pub(crate) fn token(&self) -> &str {
+        &self.token
+    }
+
+    fn make_url(&self, path: impl AsRef<str>) -> String {
+        format!("{}/{}", self.endpoints.api, path.as_ref())
+    }
+
+    /// Make a `GET` request to the API and convert the response body to JSON.
+    pub async fn get<T: DeserializeOwned>(&self, path: impl AsRef<str>) -> Result<T> {
+        let response = self.send(Method::GET, path.as_ref(), |req| req).await?;
+
+        let body = response.json().await?;
+
+        Ok(body)
+    }
+
+    /// Make a `GET` request to the API with query parameters and convert the response body to JSON.
+    pub async fn get_with_query<T: DeserializeOwned, Q: Serialize>(
+        &self,
+        path: impl AsRef<str>,
+        query: &Q,
+    ) -> Result<T> {
+        let response = self
+            .send(Method::GET, path.as_ref(), |req| req.query(query))
+            .await?;
+
+        let body = response.json().await?;
+
+        Ok(body)
+    }
+
+    /// Make a `POST` request to the API with a json body and convert the response body to JSON.
+    pub async fn post<T: DeserializeOwned, U: Serialize>(
+        &self,
+        path: impl AsRef<str>,
+        body: U,
+    ) -> Result<T> {
+        let response = self
+            .send(Method::POST, path.as_ref(), |req| req.json(&body))
+            .await?;
+
+        let body = response.json().await?;
+
+        Ok(body)
+    }
+
+    /// Make a `PUT` request to the API with a JSON body.
+    pub async fn put<T: Serialize>(&self, path: impl AsRef<str>, body: T) -> Result<()> {
+        self.send(Method::PUT, path.as_ref(), |req| req.json(&body))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Make a `PUT` request to the API without body.
+    pub async fn put_empty(&self, path: impl AsRef<str>) -> Result<()> {
+        self.send(Method::PUT, path.as_ref(), |req| req).await?;
+
+        Ok(())
+    }
+
+    /// Make a `PATCH` request to the API with a JSON body.
+    pub async fn patch<T: Serialize>(&self, path: impl AsRef<str>, body: T) -> Result<()> {
+        self.send(Method::PATCH, path.as_ref(), |req| req.json(&body))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Make a `DELETE` request to the API.
+    pub async fn delete(&self, path: impl AsRef<str>) -> Result<()> {
+        self.send(Method::DELETE, path.as_ref(), |req| req).await?;
+
+        Ok(())
+    }
+
+    /// Make a `DELETE` request to the API with query parameters.
+    pub async fn delete_with_query<Q: Serialize>(
+        &self,
+        path: impl AsRef<str>,
+        query: &Q,
+    ) -> Result<()> {
+        self.send(Method::DELETE, path.as_ref(), |req| req.query(query))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Make a `DELETE` request to the API with a JSON body.
+    pub async fn delete_with_body<T: Serialize>(
+        &self,
+        path: impl AsRef<str>,
+        body: T,
+    ) -> Result<()> {
+        self.send(Method::DELETE, path.as_ref(), |req| req.json(&body))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Send a request to the API once its rate limit bucket allows it,
+    /// and retry it according to the [`RetryPolicy`] if it fails.
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        build: impl Fn(RequestBuilder) -> RequestBuilder,
+    ) -> Result<Response> {
+        let url = self.make_url(path);
+
+        self.send_to(method, &url, path, |request| Ok(build(request)))
+            .await
+    }
+
+    /// Send a request to the specified url once the rate limit bucket of `path` allows it,
+    /// and retry it according to the [`RetryPolicy`] if it fails.
+    ///
+    /// `build` is called for every attempt. If it fails when retrying,
+    /// for example because a streamed body was already sent, the error of the last attempt is returned.
+    async fn send_to(
+        &self,
+        method: Method,
+        url: &str,
+        path: &str,
+        mut build: impl FnMut(RequestBuilder) -> Result<RequestBuilder>,
+    ) -> Result<Response> {
+        let route = ratelimit::route(&method, path);
+        let mut request = build(self.client.request(method.clone(), url))?;
+        let mut retry = 0;
+
+        loop {
+            let discovery = self.rate_limiter.acquire(&route).await;
+
+            let response = request.send().await?;
+            self.rate_limiter.update(&route, response.headers());
+            drop(discovery);
+            let retry_after = retry::retry_after(response.headers());
+
+            let err =
+                match Self::check_response(response, format!("{} /{}", method, path), retry_after)
+                    .await
+                {
+                    Ok(response) => return Ok(response),
+                    Err(err) => err,
+                };
+
+            retry += 1;
+            let delay = match err {
+                RSError::RateLimited { retry_after, .. } => retry_after,
+                _ if retry::is_idempotent(&method)
+                    && err.status().is_some_and(|status| status.is_server_error()) =>
+                {
+                    retry_after.unwrap_or_else(|| self.retry_policy.delay(retry))
+                }
+                _ => return Err(err),
+            };
+
+            if !self.retry_policy.allows(retry) {
+                return Err(err);
+            }
+
+            request = match build(self.client.request(method.clone(), url)) {
+                Ok(request) => request,
+                Err(_) => return Err(err),
+            };
+
+            warn!(
+                target: "HttpClient",
+                "Retrying {} /{} in {:?}: {}", method, path, delay, err
+            );
+            sleep(delay).await;
+        }
+    }
+
+    /// Converts an unsuccessful response to an error.
+    ///
+    /// `retry_after` is the delay of the response's `Retry-After` header,
+    /// used when a rate limited body does not carry one.
+    async fn check_response(
+        response: Response,
+        route: String,
+        retry_after: Option<Duration>,
+    ) -> Result<Response> {
+        let status = response.status();
+
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let body = response.text().await?;
+
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = serde_json::from_str::<RateLimited>(&body)
+                .map(|body| Duration::from_millis(body.retry_after))
+                .ok()
+                .or(retry_after)
+                .unwrap_or_default();
+
+            return Err(RSError::RateLimited { route, retry_after });
+        }
+
+        match serde_json::from_str::<APIError>(&body) {
+            Ok(error) => Err(RSError::Api {
+                route,
+                status,
+                error,
+            }),
+            Err(_) => Err(RSError::UnexpectedResponse {
+                route,
+                status,
+                body,
+            }),
+        }
+    }
+}
+
+/// Body of a rate limited response.
+#[derive(Debug, Deserialize)]
+struct RateLimited {
+    retry_after: u64,
+}
@@ -0,0 +1,337 @@
+//! Rate limiting of the requests to the Revolt REST API.
+//!
+//! Revolt groups routes into buckets, and tells in the response headers which bucket
+//! a route belongs to, how many requests are left in it and when it resets.
+//! Requests made to a bucket without remaining requests wait until the bucket resets.
+//! Until the bucket of a route is known, requests to it are sent one at a time.
+
+use {
+    ahash::AHashMap,
+    reqwest::{header::HeaderMap, Method},
+    std::{
+        sync::{Arc, Mutex as StdMutex},
+        time::Duration,
+    },
+    tokio::{
+        sync::{Mutex, OwnedMutexGuard},
+        time::{sleep_until, Instant},
+    },
+    tracing::debug,
+};
+
+const BUCKET: &str = "X-RateLimit-Bucket";
+const LIMIT: &str = "X-RateLimit-Limit";
+const REMAINING: &str = "X-RateLimit-Remaining";
+const RESET_AFTER: &str = "X-RateLimit-Reset-After";
+
+/// How long to wait for an exhausted bucket whose reset is not known.
+const DEFAULT_RESET_AFTER: Duration = Duration::from_secs(1);
+
+/// Rate limit state shared between all clones of an [`HttpClient`](super::HttpClient).
+#[derive(Debug, Default)]
+pub(crate) struct RateLimiter {
+    /// Buckets learned from the responses, by route, `None` if the route is not rate limited.
+    routes: StdMutex<AHashMap<String, Option<String>>>,
+    /// Held while waiting for the first response of a route, by route.
+    discovery: StdMutex<AHashMap<String, Arc<Mutex<()>>>>,
+    /// Buckets state, by bucket id.
+    buckets: StdMutex<AHashMap<String, Arc<Bucket>>>,
+}
+
+#[derive(Debug, Default)]
+struct Bucket {
+    /// Held while waiting for the bucket, so requests wait in order.
+    queue: Mutex<()>,
+    state: StdMutex<BucketState>,
+}
+
+#[derive(Debug, Default)]
+struct BucketState {
+    limit: Option<u32>,
+    remaining: Option<u32>,
+    reset_at: Option<Instant>,
+}
+
+impl RateLimiter {
+    /// Waits until a request can be made to the specified route.
+    ///
+    /// Requests to the same bucket are queued in the order they were made.
+    /// If the bucket of the route is not known yet, the returned guard must be held until
+    /// the headers of the response are passed to [`update`](Self::update).
+    pub(crate) async fn acquire(&self, route: &str) -> Option<OwnedMutexGuard<()>> {
+        loop {
+            let known = self.routes.lock().unwrap().get(route).cloned();
+
+            match known {
+                Some(Some(id)) => {
+                    let bucket = self.buckets.lock().unwrap().get(&id).cloned()?;
+                    bucket.acquire(route).await;
+
+                    return None;
+                }
+                Some(None) => return None,
+                None => {
+                    let discovery = self
+                        .discovery
+                        .lock()
+                        .unwrap()
+                        .entry(route.to_owned())
+                        .or_default()
+                        .clone();
+                    let guard = discovery.lock_owned().await;
+
+                    // Another request may have learned the bucket while this one was waiting.
+                    if !self.routes.lock().unwrap().contains_key(route) {
+                        return Some(guard);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Updates the bucket of the specified route from the response headers.
+    pub(crate) fn update(&self, route: &str, headers: &HeaderMap) {
+        let Some(id) = header(headers, BUCKET) else {
+            self.routes
+                .lock()
+                .unwrap()
+                .entry(route.to_owned())
+                .or_insert(None);
+            self.discovery.lock().unwrap().remove(route);
+            return;
+        };
+
+        self.routes
+            .lock()
+            .unwrap()
+            .insert(route.to_owned(), Some(id.to_owned()));
+        self.discovery.lock().unwrap().remove(route);
+
+        let bucket = self
+            .buckets
+            .lock()
+            .unwrap()
+            .entry(id.to_owned())
+            .or_default()
+            .clone();
+
+        let mut state = bucket.state.lock().unwrap();
+
+        if let Some(limit) = header(headers, LIMIT).and_then(|limit| limit.parse().ok()) {
+            state.limit = Some(limit);
+        }
+
+        if let Some(remaining) = header(headers, REMAINING).and_then(|value| value.parse().ok()) {
+            state.remaining = Some(remaining);
+        }
+
+        if let Some(reset_after) = header(headers, RESET_AFTER).and_then(|value| value.parse().ok())
+        {
+            state.reset_at = Some(Instant::now() + Duration::from_millis(reset_after));
+        }
+    }
+}
+
+impl Bucket {
+    /// Waits until the bucket has a remaining request, and takes it.
+    async fn acquire(&self, route: &str) {
+        let _queue = self.queue.lock().await;
+
+        let reset_at = {
+            let mut state = self.state.lock().unwrap();
+
+            match state.remaining {
+                Some(0) => state
+                    .reset_at
+                    .take()
+                    .unwrap_or_else(|| Instant::now() + DEFAULT_RESET_AFTER),
+                Some(ref mut remaining) => {
+                    *remaining -= 1;
+                    return;
+                }
+                None => return,
+            }
+        };
+
+        debug!(
+            target: "RateLimiter",
+            "Waiting {:?} for {}",
+            reset_at.saturating_duration_since(Instant::now()),
+            route
+        );
+        sleep_until(reset_at).await;
+
+        let mut state = self.state.lock().unwrap();
+        state.remaining = state.limit.map(|limit| limit.saturating_sub(1));
+    }
+}
+
+/// Returns the route of a request, with the ids replaced so that
+/// requests to different resources of the same kind share the same route.
+pub(crate) fn route(method: &Method, path: &str) -> String {
+    let path = path
+        .split('/')
+        .map(|segment| if is_id(segment) { ":id" } else { segment })
+        .collect::<Vec<_>>()
+        .join("/");
+
+    format!("{} /{}", method, path)
+}
+
+/// Whether the path segment is an ULID.
+fn is_id(segment: &str) -> bool {
+    segment.len() == 26 && segment.bytes().all(|byte| byte.is_ascii_alphanumeric())
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name)?.to_str().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        reqwest::header::{HeaderName, HeaderValue},
+    };
+
+    fn headers(values: &[(&str, &str)]) -> HeaderMap {
+        values
+            .iter()
+            .map(|(name, value)| {
+                (
+                    HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                    HeaderValue::from_str(value).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    fn state(limiter: &RateLimiter, id: &str) -> (Option<u32>, Option<u32>, Option<Instant>) {
+        let bucket = limiter.buckets.lock().unwrap().get(id).cloned().unwrap();
+        let state = bucket.state.lock().unwrap();
+
+        (state.limit, state.remaining, state.reset_at)
+    }
+
+    #[test]
+    fn route_replaces_ids() {
+        assert_eq!(
+            route(&Method::GET, "channels/01HZ8N7K3M5XTQW9RFYB2C4D6E/messages"),
+            "GET /channels/:id/messages"
+        );
+        assert_eq!(route(&Method::POST, "users/@me"), "POST /users/@me");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn update_parses_headers() {
+        let limiter = RateLimiter::default();
+        limiter.update(
+            "GET /users/:id",
+            &headers(&[
+                (BUCKET, "users"),
+                (LIMIT, "20"),
+                (REMAINING, "19"),
+                (RESET_AFTER, "1500"),
+            ]),
+        );
+
+        let (limit, remaining, reset_at) = state(&limiter, "users");
+        assert_eq!(limit, Some(20));
+        assert_eq!(remaining, Some(19));
+        assert_eq!(reset_at, Some(Instant::now() + Duration::from_millis(1500)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn update_ignores_invalid_headers() {
+        let limiter = RateLimiter::default();
+        limiter.update(
+            "GET /users/:id",
+            &headers(&[(BUCKET, "users"), (LIMIT, "many"), (REMAINING, "-1")]),
+        );
+
+        assert_eq!(state(&limiter, "users"), (None, None, None));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn unknown_bucket_is_not_exhausted() {
+        let limiter = RateLimiter::default();
+        limiter.update("GET /users/:id", &headers(&[(BUCKET, "users")]));
+
+        let start = Instant::now();
+        for _ in 0..3 {
+            assert!(limiter.acquire("GET /users/:id").await.is_none());
+        }
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn exhausted_bucket_waits_for_reset() {
+        let limiter = RateLimiter::default();
+        limiter.update(
+            "GET /users/:id",
+            &headers(&[
+                (BUCKET, "users"),
+                (LIMIT, "2"),
+                (REMAINING, "1"),
+                (RESET_AFTER, "1000"),
+            ]),
+        );
+
+        let start = Instant::now();
+        limiter.acquire("GET /users/:id").await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        limiter.acquire("GET /users/:id").await;
+        assert_eq!(start.elapsed(), Duration::from_millis(1000));
+        assert_eq!(state(&limiter, "users").1, Some(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn exhausted_bucket_without_reset_waits_default() {
+        let limiter = RateLimiter::default();
+        limiter.update(
+            "GET /users/:id",
+            &headers(&[(BUCKET, "users"), (LIMIT, "5"), (REMAINING, "0")]),
+        );
+
+        let start = Instant::now();
+        limiter.acquire("GET /users/:id").await;
+        assert_eq!(start.elapsed(), DEFAULT_RESET_AFTER);
+        assert_eq!(state(&limiter, "users").1, Some(4));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn first_request_of_a_route_is_serialized() {
+        let limiter = Arc::new(RateLimiter::default());
+        let discovery = limiter.acquire("GET /users/:id").await;
+        assert!(discovery.is_some());
+
+        let second = tokio::spawn({
+            let limiter = limiter.clone();
+            async move { limiter.acquire("GET /users/:id").await.is_none() }
+        });
+
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        assert!(!second.is_finished());
+
+        limiter.update(
+            "GET /users/:id",
+            &headers(&[(BUCKET, "users"), (LIMIT, "10"), (REMAINING, "9")]),
+        );
+        drop(discovery);
+
+        assert!(second.await.unwrap());
+        assert_eq!(state(&limiter, "users").1, Some(8));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn routes_without_bucket_are_not_limited() {
+        let limiter = RateLimiter::default();
+        let discovery = limiter.acquire("GET /").await;
+        limiter.update("GET /", &HeaderMap::new());
+        drop(discovery);
+
+        assert!(limiter.acquire("GET /").await.is_none());
+        assert!(limiter.acquire("GET /").await.is_none());
+    }
+}
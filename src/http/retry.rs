@@ -0,0 +1,93 @@
+use {
+    crate::backoff::Backoff,
+    reqwest::{header::HeaderMap, Method},
+    std::time::Duration,
+};
+
+/// Policy used by the [`HttpClient`](super::HttpClient) to retry failed requests.
+///
+/// Rate limited requests are retried after the delay given by the API.
+/// Requests with an idempotent method (`GET`, `PUT` and `DELETE`) are also retried
+/// when the API returns a server error, with a delay growing exponentially from
+/// [`min_backoff`](Self::min_backoff) up to [`max_backoff`](Self::max_backoff).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of retries of a request.
+    pub max_retries: u32,
+    /// Delay before the first retry after a server error.
+    pub min_backoff: Duration,
+    /// Upper bound of the delay between retries after a server error.
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Creates a new [`RetryPolicy`].
+    pub fn new(max_retries: u32, min_backoff: Duration, max_backoff: Duration) -> Self {
+        Self {
+            max_retries,
+            min_backoff,
+            max_backoff,
+        }
+    }
+
+    /// A policy that never retries.
+    pub fn disabled() -> Self {
+        Self {
+            max_retries: 0,
+            ..Default::default()
+        }
+    }
+
+    /// Set the maximum number of retries of a request.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the lower and upper bounds of the delay between retries after a server error.
+    pub fn backoff(mut self, min: Duration, max: Duration) -> Self {
+        self.min_backoff = min;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Whether the specified retry (starting from `1`) is allowed by the policy.
+    pub(crate) fn allows(&self, retry: u32) -> bool {
+        retry <= self.max_retries
+    }
+
+    /// Returns the delay to wait before the specified retry (starting from `1`) after a server error.
+    pub(crate) fn delay(&self, retry: u32) -> Duration {
+        Backoff::new(self.min_backoff, self.max_backoff).delay(retry)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            min_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Whether a request with the specified method can be sent again without side effects.
+pub(crate) fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::PUT | Method::DELETE
+    )
+}
+
+/// Returns the delay given by the `Retry-After` header, in seconds.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get("Retry-After")?
+        .to_str()
+        .ok()?
+        .parse::<f64>()
+        .ok()
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map(Duration::from_secs_f64)
+}
@@ -8,17 +8,17 @@
 pub use client::*;
 mod client;
 
-#[doc(hidden)]
 pub mod error;
 
-/// Revolt API Permissions
-pub mod api_permissions;
 pub mod builders;
 #[cfg(feature = "cache")]
 pub mod cache;
 pub mod http;
 pub mod models;
+#[cfg(feature = "reaction_roles")]
+pub mod reaction_roles;
 #[cfg(feature = "state")]
 pub mod state;
 
+pub(crate) mod backoff;
 pub(crate) mod websocket;
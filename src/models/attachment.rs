@@ -1,6 +1,15 @@
-use serde::Deserialize;
+use {
+    bytes::Bytes,
+    futures_util::{stream::BoxStream, StreamExt, TryStreamExt},
+    percent_encoding::utf8_percent_encode,
+    serde::Deserialize,
+};
 
-use crate::models::ID;
+use crate::{
+    error::{RSError, Result},
+    models::{ID, PATH_SEGMENT},
+    Context,
+};
 
 /// An attachment like icons, avatars, banners or message attachments.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -20,8 +29,86 @@ pub struct Attachment {
     pub content_type: String,
 }
 
+impl Attachment {
+    /// Returns the url of the original file on the file server (Autumn).
+    pub fn url(&self, ctx: &Context) -> String {
+        format!(
+            "{}/{}",
+            self.preview_url(ctx),
+            utf8_percent_encode(&self.filename, PATH_SEGMENT)
+        )
+    }
+
+    /// Returns the url of the preview of the file on the file server (Autumn).
+    ///
+    /// Images are resized to a smaller size, other files are served as is.
+    pub fn preview_url(&self, ctx: &Context) -> String {
+        format!(
+            "{}/{}/{}",
+            ctx.http_client.endpoints().autumn,
+            self.tag.as_str(),
+            self.id
+        )
+    }
+
+    /// Download the content of the attachment.
+    ///
+    /// Fails with [`RSError::AttachmentTooLarge`] if the attachment is larger than `max_size` bytes,
+    /// or if the server sends more bytes than the attachment [`size`](Self::size).
+    pub async fn download(&self, ctx: &Context, max_size: Option<usize>) -> Result<Vec<u8>> {
+        let mut stream = self.stream(ctx, max_size).await?;
+        let mut content = Vec::with_capacity(self.size);
+
+        while let Some(chunk) = stream.try_next().await? {
+            content.extend_from_slice(&chunk);
+        }
+
+        Ok(content)
+    }
+
+    /// Stream the content of the attachment, without keeping it in memory.
+    ///
+    /// Fails with [`RSError::AttachmentTooLarge`] if the attachment is larger than `max_size` bytes,
+    /// or if the server sends more bytes than the attachment [`size`](Self::size).
+    pub async fn stream(
+        &self,
+        ctx: &Context,
+        max_size: Option<usize>,
+    ) -> Result<BoxStream<'static, Result<Bytes>>> {
+        if let Some(max_size) = max_size.filter(|max_size| self.size > *max_size) {
+            return Err(RSError::AttachmentTooLarge {
+                size: self.size,
+                max_size,
+            });
+        }
+
+        let path = format!("{}/{}", self.tag.as_str(), self.id);
+        let response = ctx.http_client.download(&self.url(ctx), &path).await?;
+
+        let max_size = self.size;
+        let mut received = 0;
+
+        Ok(response
+            .bytes_stream()
+            .map(move |chunk| {
+                let chunk = chunk?;
+                received += chunk.len();
+
+                if received > max_size {
+                    return Err(RSError::AttachmentTooLarge {
+                        size: received,
+                        max_size,
+                    });
+                }
+
+                Ok(chunk)
+            })
+            .boxed())
+    }
+}
+
 /// Attachment tag.
-#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum AttachmentTag {
     /// Attachments tag.
@@ -36,6 +123,19 @@ pub enum AttachmentTag {
     Banners,
 }
 
+impl AttachmentTag {
+    /// Returns the tag as used in the urls of the file server.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Attachments => "attachments",
+            Self::Avatars => "avatars",
+            Self::Backgrounds => "backgrounds",
+            Self::Icons => "icons",
+            Self::Banners => "banners",
+        }
+    }
+}
+
 /// Attachment metadata.
 #[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
 #[serde(tag = "type")]
@@ -2,11 +2,11 @@ use serde::Deserialize;
 
 use crate::{
     builders::CreateMessage,
-    models::{Channel, Message, ID},
+    models::{Channel, Message, MessageHistory, ID},
     Context,
 };
 
-use anyhow::Result;
+use crate::error::Result;
 
 /// A DM channel.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -33,6 +33,11 @@ impl DirectMessageChannel {
         Message::create(ctx, &self.id, builder.into()).await
     }
 
+    /// Returns a query for the message history of this channel.
+    pub fn history(&self) -> MessageHistory {
+        MessageHistory::new(&self.id)
+    }
+
     /// Close the DM.
     pub async fn close(&self, ctx: &Context) -> Result<()> {
         Channel::delete(ctx, &self.id).await
@@ -1,12 +1,12 @@
 use serde::Deserialize;
 
 use crate::{
-    builders::{CreateMessage, EditChannel},
-    models::{Attachment, Channel, Message, User, ID},
+    builders::{CreateMessage, EditChannel, MessageSearch, SetPermissions},
+    models::{Attachment, Channel, Message, MessageBatch, MessageHistory, Permissions, User, ID},
     Context,
 };
 
-use anyhow::Result;
+use crate::error::Result;
 
 /// A group channel.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -30,6 +30,9 @@ pub struct GroupChannel {
     /// Group is not safe for work.
     #[serde(default)]
     pub nsfw: bool,
+    /// Permissions given to every member of the group, as groups have no roles.
+    #[serde(rename = "permissions")]
+    pub default_permissions: Option<Permissions>,
 }
 
 impl GroupChannel {
@@ -45,11 +48,34 @@ impl GroupChannel {
         Message::create(ctx, &self.id, builder.into()).await
     }
 
+    /// Search the messages of the group.
+    pub async fn search(
+        &self,
+        ctx: &Context,
+        builder: impl Into<MessageSearch>,
+    ) -> Result<MessageBatch> {
+        Message::search(ctx, &self.id, builder.into()).await
+    }
+
+    /// Returns a query for the message history of the group.
+    pub fn history(&self) -> MessageHistory {
+        MessageHistory::new(&self.id)
+    }
+
     /// Edit the group.
     pub async fn edit(&self, ctx: &Context, builder: EditChannel) -> Result<()> {
         Channel::edit(ctx, &self.id, builder).await
     }
 
+    /// Set the permissions given to every member of the group.
+    pub async fn set_default_permissions(
+        &self,
+        ctx: &Context,
+        permissions: Permissions,
+    ) -> Result<()> {
+        Channel::set_default_permissions(ctx, &self.id, SetPermissions::value(permissions)).await
+    }
+
     /// Leave the group.
     pub async fn leave(&self, ctx: &Context) -> Result<()> {
         Channel::delete(ctx, &self.id).await
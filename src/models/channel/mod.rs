@@ -7,9 +7,13 @@ mod voice;
 
 use serde::Deserialize;
 
-use crate::{builders::EditChannel, models::ID, Context};
+use crate::{
+    builders::{EditChannel, SetPermissions},
+    models::{Override, ID},
+    Context,
+};
 
-use anyhow::Result;
+use crate::error::Result;
 
 #[cfg(feature = "cache")]
 use crate::cache::UpdateCache;
@@ -51,6 +55,15 @@ impl Channel {
         }
     }
 
+    /// Returns the id of the server of the channel, if the channel belongs to a server.
+    pub fn server_id(&self) -> Option<&ID> {
+        match self {
+            Self::Text(TextChannel { server_id, .. })
+            | Self::Voice(VoiceChannel { server_id, .. }) => Some(server_id),
+            _ => None,
+        }
+    }
+
     async fn edit(ctx: &Context, channel_id: &ID, builder: EditChannel) -> Result<()> {
         ctx.http_client
             .patch(format!("channels/{}", channel_id), builder)
@@ -63,33 +76,64 @@ impl Channel {
             .await
     }
 
-    pub fn get_text_channel(self) -> Option<TextChannel>{
-        if let Self::Text(TextChannel) = self {
-            Some(TextChannel)
+    async fn set_role_permissions(
+        ctx: &Context,
+        channel_id: &ID,
+        role_id: &ID,
+        permissions: Override,
+    ) -> Result<()> {
+        ctx.http_client
+            .put(
+                format!("channels/{}/permissions/{}", channel_id, role_id),
+                SetPermissions::allow_deny(permissions),
+            )
+            .await
+    }
+
+    async fn set_default_permissions(
+        ctx: &Context,
+        channel_id: &ID,
+        permissions: SetPermissions,
+    ) -> Result<()> {
+        ctx.http_client
+            .put(
+                format!("channels/{}/permissions/default", channel_id),
+                permissions,
+            )
+            .await
+    }
+
+    /// Returns the inner [`TextChannel`] if the channel is a text channel.
+    pub fn get_text_channel(self) -> Option<TextChannel> {
+        if let Self::Text(text_channel) = self {
+            Some(text_channel)
         } else {
             None
         }
     }
 
-    pub fn get_voice_channel(self) -> Option<VoiceChannel>{
-        if let Self::Voice(VoiceChannel) = self {
-            Some(VoiceChannel)
+    /// Returns the inner [`VoiceChannel`] if the channel is a voice channel.
+    pub fn get_voice_channel(self) -> Option<VoiceChannel> {
+        if let Self::Voice(voice_channel) = self {
+            Some(voice_channel)
         } else {
             None
         }
     }
 
-    pub fn get_group_channel(self) -> Option<GroupChannel>{
-        if let Self::Group(GroupChannel) = self {
-            Some(GroupChannel)
+    /// Returns the inner [`GroupChannel`] if the channel is a group channel.
+    pub fn get_group_channel(self) -> Option<GroupChannel> {
+        if let Self::Group(group_channel) = self {
+            Some(group_channel)
         } else {
             None
         }
     }
 
-    pub fn get_dm_channel(self) -> Option<DirectMessageChannel>{
-        if let Self::DirectMessage(DirectMessageChannel) = self {
-            Some(DirectMessageChannel)
+    /// Returns the inner [`DirectMessageChannel`] if the channel is a DM channel.
+    pub fn get_dm_channel(self) -> Option<DirectMessageChannel> {
+        if let Self::DirectMessage(dm_channel) = self {
+            Some(dm_channel)
         } else {
             None
         }
@@ -1,12 +1,12 @@
-use serde::Deserialize;
+use {ahash::AHashMap, serde::Deserialize};
 
 use crate::{
-    builders::{CreateMessage, EditChannel},
-    models::{Attachment, Channel, Message, ID},
+    builders::{CreateMessage, EditChannel, MessageSearch, SetPermissions},
+    models::{Attachment, Channel, Message, MessageBatch, MessageHistory, Override, Purge, ID},
     Context,
 };
 
-use anyhow::Result;
+use crate::error::Result;
 
 /// A text channel.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -28,6 +28,11 @@ pub struct TextChannel {
     /// Channel is not safe for work (+18).
     #[serde(default)]
     pub nsfw: bool,
+    /// Permissions override applied to every member.
+    pub default_permissions: Option<Override>,
+    /// Permissions overrides applied to the members of a role, by role id.
+    #[serde(default)]
+    pub role_permissions: AHashMap<ID, Override>,
 }
 
 impl TextChannel {
@@ -36,6 +41,32 @@ impl TextChannel {
         Message::create(ctx, &self.id, builder.into()).await
     }
 
+    /// Search the messages of this channel.
+    pub async fn search(
+        &self,
+        ctx: &Context,
+        builder: impl Into<MessageSearch>,
+    ) -> Result<MessageBatch> {
+        Message::search(ctx, &self.id, builder.into()).await
+    }
+
+    /// Returns a query for the message history of this channel.
+    pub fn history(&self) -> MessageHistory {
+        MessageHistory::new(&self.id)
+    }
+
+    /// Delete the specified messages of this channel in bulk.
+    ///
+    /// The messages must have been sent in the past week.
+    pub async fn delete_messages(&self, ctx: &Context, ids: &[ID]) -> Result<()> {
+        Message::delete_many(ctx, &self.id, ids).await
+    }
+
+    /// Returns a [`Purge`] of the recent messages of this channel.
+    pub fn purge(&self) -> Purge {
+        Purge::new(&self.id)
+    }
+
     /// Edit the channel.
     pub async fn edit(&self, ctx: &Context, builder: EditChannel) -> Result<()> {
         Channel::edit(ctx, &self.id, builder).await
@@ -45,4 +76,24 @@ impl TextChannel {
     pub async fn delete(&self, ctx: &Context) -> Result<()> {
         Channel::delete(ctx, &self.id).await
     }
+
+    /// Set the permissions allowed and denied by a role of the server in the channel.
+    pub async fn set_role_permissions(
+        &self,
+        ctx: &Context,
+        role_id: &ID,
+        permissions: Override,
+    ) -> Result<()> {
+        Channel::set_role_permissions(ctx, &self.id, role_id, permissions).await
+    }
+
+    /// Set the permissions allowed and denied to every member in the channel.
+    pub async fn set_default_permissions(
+        &self,
+        ctx: &Context,
+        permissions: Override,
+    ) -> Result<()> {
+        Channel::set_default_permissions(ctx, &self.id, SetPermissions::allow_deny(permissions))
+            .await
+    }
 }
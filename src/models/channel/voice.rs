@@ -1,12 +1,12 @@
-use serde::Deserialize;
+use {ahash::AHashMap, serde::Deserialize};
 
 use crate::{
-    builders::EditChannel,
-    models::{Attachment, Channel, ID},
+    builders::{EditChannel, SetPermissions},
+    models::{Attachment, Channel, Override, ID},
     Context,
 };
 
-use anyhow::Result;
+use crate::error::Result;
 
 /// A voice channel.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -26,6 +26,11 @@ pub struct VoiceChannel {
     /// Channel is not safe for work.
     #[serde(default)]
     pub nsfw: bool,
+    /// Permissions override applied to every member.
+    pub default_permissions: Option<Override>,
+    /// Permissions overrides applied to the members of a role, by role id.
+    #[serde(default)]
+    pub role_permissions: AHashMap<ID, Override>,
 }
 
 impl VoiceChannel {
@@ -38,4 +43,24 @@ impl VoiceChannel {
     pub async fn delete(&self, ctx: &Context) -> Result<()> {
         Channel::delete(ctx, &self.id).await
     }
+
+    /// Set the permissions allowed and denied by a role of the server in the channel.
+    pub async fn set_role_permissions(
+        &self,
+        ctx: &Context,
+        role_id: &ID,
+        permissions: Override,
+    ) -> Result<()> {
+        Channel::set_role_permissions(ctx, &self.id, role_id, permissions).await
+    }
+
+    /// Set the permissions allowed and denied to every member in the channel.
+    pub async fn set_default_permissions(
+        &self,
+        ctx: &Context,
+        permissions: Override,
+    ) -> Result<()> {
+        Channel::set_default_permissions(ctx, &self.id, SetPermissions::allow_deny(permissions))
+            .await
+    }
 }
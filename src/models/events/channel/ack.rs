@@ -5,7 +5,7 @@ use crate::{
     Context,
 };
 
-use anyhow::Result;
+use crate::error::Result;
 
 /// You have acknowledged new messages in the channel up to the message id.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
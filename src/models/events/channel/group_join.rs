@@ -5,7 +5,7 @@ use crate::{
     Context,
 };
 
-use anyhow::Result;
+use crate::error::Result;
 
 #[cfg(feature = "cache")]
 use crate::cache::UpdateCache;
@@ -5,7 +5,7 @@ use crate::{
     Context,
 };
 
-use anyhow::Result;
+use crate::error::Result;
 
 /// A user has started typing in a channel.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -1,11 +1,14 @@
-use serde::{Deserialize, Serialize};
+use {
+    ahash::AHashMap,
+    serde::{Deserialize, Serialize},
+};
 
 use crate::{
-    models::{Attachment, Channel, ID},
+    models::{Attachment, Channel, Override, Permissions, ID},
     Context,
 };
 
-use anyhow::Result;
+use crate::error::Result;
 
 #[cfg(feature = "cache")]
 use crate::cache::UpdateCache;
@@ -17,6 +20,8 @@ pub enum ChannelField {
     Icon,
     /// Channel description,
     Description,
+    /// Channel default permissions.
+    DefaultPermissions,
 }
 
 /// A channel details were updated.
@@ -27,8 +32,9 @@ pub struct ChannelUpdateEvent {
     pub channel_id: ID,
     /// A partial channel.
     pub data: PartialChannel,
-    /// A specified field to remove on channel update.
-    pub clear: Option<ChannelField>,
+    /// The fields to remove on channel update.
+    #[serde(default)]
+    pub clear: Vec<ChannelField>,
 }
 
 impl ChannelUpdateEvent {
@@ -49,6 +55,13 @@ pub struct PartialChannel {
     pub icon: Option<Attachment>,
     /// Whether channel is not safe for work.
     pub nsfw: Option<bool>,
+    /// Channel default permissions.
+    pub default_permissions: Option<Override>,
+    /// Channel role permissions, by role id.
+    pub role_permissions: Option<AHashMap<ID, Override>>,
+    /// Group default permissions.
+    #[serde(rename = "permissions")]
+    pub group_permissions: Option<Permissions>,
 }
 
 #[cfg(feature = "cache")]
@@ -58,10 +71,11 @@ impl UpdateCache for ChannelUpdateEvent {
         if let Some(channel) = ctx.cache.channels.write().await.get_mut(&self.channel_id) {
             match channel {
                 Channel::Group(channel) => {
-                    if let Some(field) = self.clear {
+                    for field in &self.clear {
                         match field {
                             ChannelField::Description => channel.description = None,
                             ChannelField::Icon => channel.icon = None,
+                            ChannelField::DefaultPermissions => channel.default_permissions = None,
                         }
                     }
 
@@ -80,12 +94,17 @@ impl UpdateCache for ChannelUpdateEvent {
                     if let Some(nsfw) = self.data.nsfw {
                         channel.nsfw = nsfw;
                     }
+
+                    if let Some(permissions) = self.data.group_permissions {
+                        channel.default_permissions = Some(permissions);
+                    }
                 }
                 Channel::Text(channel) => {
-                    if let Some(field) = self.clear {
+                    for field in &self.clear {
                         match field {
                             ChannelField::Description => channel.description = None,
                             ChannelField::Icon => channel.icon = None,
+                            ChannelField::DefaultPermissions => channel.default_permissions = None,
                         }
                     }
 
@@ -104,12 +123,21 @@ impl UpdateCache for ChannelUpdateEvent {
                     if let Some(nsfw) = self.data.nsfw {
                         channel.nsfw = nsfw;
                     }
+
+                    if let Some(permissions) = self.data.default_permissions {
+                        channel.default_permissions = Some(permissions);
+                    }
+
+                    if let Some(ref permissions) = self.data.role_permissions {
+                        channel.role_permissions = permissions.clone();
+                    }
                 }
                 Channel::Voice(channel) => {
-                    if let Some(field) = self.clear {
+                    for field in &self.clear {
                         match field {
                             ChannelField::Description => channel.description = None,
                             ChannelField::Icon => channel.icon = None,
+                            ChannelField::DefaultPermissions => channel.default_permissions = None,
                         }
                     }
 
@@ -128,9 +156,58 @@ impl UpdateCache for ChannelUpdateEvent {
                     if let Some(nsfw) = self.data.nsfw {
                         channel.nsfw = nsfw;
                     }
+
+                    if let Some(permissions) = self.data.default_permissions {
+                        channel.default_permissions = Some(permissions);
+                    }
+
+                    if let Some(ref permissions) = self.data.role_permissions {
+                        channel.role_permissions = permissions.clone();
+                    }
                 }
                 _ => return,
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json};
+
+    #[test]
+    fn channel_update_clears_every_channel_field() {
+        let event: ChannelUpdateEvent = serde_json::from_value(json!({
+            "id": "01J0C4HNL7W4J6QJ3PE9AB1ZK2",
+            "data": { "nsfw": true },
+            "clear": ["Description", "Icon", "DefaultPermissions"],
+        }))
+        .unwrap();
+
+        assert_eq!(
+            event.clear,
+            [
+                ChannelField::Description,
+                ChannelField::Icon,
+                ChannelField::DefaultPermissions
+            ]
+        );
+        assert_eq!(event.data.nsfw, Some(true));
+    }
+
+    #[test]
+    fn channel_update_reads_group_permissions() {
+        let event: ChannelUpdateEvent = serde_json::from_value(json!({
+            "id": "01J0C4HNL7W4J6QJ3PE9AB1ZK3",
+            "data": { "permissions": Permissions::SEND_MESSAGE.bits() },
+        }))
+        .unwrap();
+
+        assert!(event.clear.is_empty());
+        assert_eq!(
+            event.data.group_permissions,
+            Some(Permissions::SEND_MESSAGE)
+        );
+        assert_eq!(event.data.default_permissions, None);
+    }
+}
@@ -5,7 +5,10 @@ use crate::{
     Context,
 };
 
-use anyhow::Result;
+use crate::error::Result;
+
+#[cfg(feature = "cache")]
+use crate::cache::UpdateCache;
 
 /// A message has been deleted.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -24,3 +27,11 @@ impl MessageDeleteEvent {
         Channel::fetch(ctx, &self.channel_id).await
     }
 }
+
+#[cfg(feature = "cache")]
+#[async_trait::async_trait]
+impl UpdateCache for MessageDeleteEvent {
+    async fn update(&self, ctx: &Context) {
+        ctx.cache.messages.write().await.remove(&self.message_id);
+    }
+}
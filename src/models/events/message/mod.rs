@@ -1,4 +1,5 @@
-pub use {delete::*, update::*};
+pub use {delete::*, react::*, update::*};
 
 mod delete;
+mod react;
 mod update;
@@ -0,0 +1,137 @@
+use serde::Deserialize;
+
+use crate::{
+    models::{Channel, Message, User, ID},
+    Context,
+};
+
+use crate::error::Result;
+
+#[cfg(feature = "cache")]
+use crate::cache::UpdateCache;
+
+/// A user has reacted to a message.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct MessageReactEvent {
+    /// Message id.
+    #[serde(rename = "id")]
+    pub message_id: ID,
+    /// Message channel id.
+    pub channel_id: ID,
+    /// User id.
+    pub user_id: ID,
+    /// Emoji id, or the emoji itself for unicode emojis.
+    pub emoji_id: String,
+}
+
+impl MessageReactEvent {
+    /// Fetch the message.
+    pub async fn message(&self, ctx: &Context) -> Result<Message> {
+        Message::fetch(ctx, &self.channel_id, &self.message_id).await
+    }
+
+    /// Fetch the channel.
+    pub async fn channel(&self, ctx: &Context) -> Result<Channel> {
+        Channel::fetch(ctx, &self.channel_id).await
+    }
+
+    /// Fetch the user.
+    pub async fn user(&self, ctx: &Context) -> Result<User> {
+        User::fetch(ctx, &self.user_id).await
+    }
+}
+
+#[cfg(feature = "cache")]
+#[async_trait::async_trait]
+impl UpdateCache for MessageReactEvent {
+    async fn update(&self, ctx: &Context) {
+        if let Some(message) = ctx.cache.messages.write().await.get_mut(&self.message_id) {
+            let users = message.reactions.entry(self.emoji_id.clone()).or_default();
+
+            if !users.contains(&self.user_id) {
+                users.push(self.user_id.clone());
+            }
+        }
+    }
+}
+
+/// A user has removed their reaction to a message.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct MessageUnreactEvent {
+    /// Message id.
+    #[serde(rename = "id")]
+    pub message_id: ID,
+    /// Message channel id.
+    pub channel_id: ID,
+    /// User id.
+    pub user_id: ID,
+    /// Emoji id, or the emoji itself for unicode emojis.
+    pub emoji_id: String,
+}
+
+impl MessageUnreactEvent {
+    /// Fetch the message.
+    pub async fn message(&self, ctx: &Context) -> Result<Message> {
+        Message::fetch(ctx, &self.channel_id, &self.message_id).await
+    }
+
+    /// Fetch the channel.
+    pub async fn channel(&self, ctx: &Context) -> Result<Channel> {
+        Channel::fetch(ctx, &self.channel_id).await
+    }
+
+    /// Fetch the user.
+    pub async fn user(&self, ctx: &Context) -> Result<User> {
+        User::fetch(ctx, &self.user_id).await
+    }
+}
+
+#[cfg(feature = "cache")]
+#[async_trait::async_trait]
+impl UpdateCache for MessageUnreactEvent {
+    async fn update(&self, ctx: &Context) {
+        if let Some(message) = ctx.cache.messages.write().await.get_mut(&self.message_id) {
+            if let Some(users) = message.reactions.get_mut(&self.emoji_id) {
+                users.retain(|id| *id != self.user_id);
+
+                if users.is_empty() {
+                    message.reactions.remove(&self.emoji_id);
+                }
+            }
+        }
+    }
+}
+
+/// All the reactions with an emoji have been removed from a message.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct MessageRemoveReactionEvent {
+    /// Message id.
+    #[serde(rename = "id")]
+    pub message_id: ID,
+    /// Message channel id.
+    pub channel_id: ID,
+    /// Emoji id, or the emoji itself for unicode emojis.
+    pub emoji_id: String,
+}
+
+impl MessageRemoveReactionEvent {
+    /// Fetch the message.
+    pub async fn message(&self, ctx: &Context) -> Result<Message> {
+        Message::fetch(ctx, &self.channel_id, &self.message_id).await
+    }
+
+    /// Fetch the channel.
+    pub async fn channel(&self, ctx: &Context) -> Result<Channel> {
+        Channel::fetch(ctx, &self.channel_id).await
+    }
+}
+
+#[cfg(feature = "cache")]
+#[async_trait::async_trait]
+impl UpdateCache for MessageRemoveReactionEvent {
+    async fn update(&self, ctx: &Context) {
+        if let Some(message) = ctx.cache.messages.write().await.get_mut(&self.message_id) {
+            message.reactions.remove(&self.emoji_id);
+        }
+    }
+}
@@ -5,7 +5,10 @@ use crate::{
     Context,
 };
 
-use anyhow::Result;
+use crate::error::Result;
+
+#[cfg(feature = "cache")]
+use crate::{cache::UpdateCache, models::Content};
 
 /// A message has been edited or otherwise updated.
 #[derive(Debug, Clone, PartialEq, Deserialize)]
@@ -43,3 +46,21 @@ pub struct PartialMessage {
     /// Message edition date.
     pub edited: MessageEdited,
 }
+
+#[cfg(feature = "cache")]
+#[async_trait::async_trait]
+impl UpdateCache for MessageUpdateEvent {
+    async fn update(&self, ctx: &Context) {
+        if let Some(message) = ctx.cache.messages.write().await.get_mut(&self.message_id) {
+            if let Some(ref content) = self.data.content {
+                message.content = Content::Text(content.clone());
+            }
+
+            if !self.data.embeds.is_empty() {
+                message.embeds = self.data.embeds.clone();
+            }
+
+            message.edited = Some(self.data.edited.clone());
+        }
+    }
+}
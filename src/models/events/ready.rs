@@ -23,28 +23,36 @@ pub struct ReadyEvent {
 #[async_trait::async_trait]
 impl UpdateCache for ReadyEvent {
     async fn update(&self, ctx: &Context) {
+        // A ready event is received after every (re)connection and carries
+        // the whole state, so the cache is rebuilt from scratch.
         let mut users = ctx.cache.users.write().await;
+        users.clear();
 
         for user in &self.users {
             users.insert(user.id.clone(), user.clone());
         }
 
         let mut channels = ctx.cache.channels.write().await;
+        channels.clear();
 
         for channel in &self.channels {
             channels.insert(channel.id().clone(), channel.clone());
         }
 
         let mut servers = ctx.cache.servers.write().await;
+        servers.clear();
 
         for server in &self.servers {
             servers.insert(server.id.clone(), server.clone());
         }
 
         let mut members = ctx.cache.members.write().await;
+        members.clear();
 
         for member in &self.members {
             members.insert(member.id.clone(), member.clone());
         }
+
+        ctx.cache.messages.write().await.clear();
     }
 }
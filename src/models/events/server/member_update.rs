@@ -5,7 +5,7 @@ use crate::{
     Context,
 };
 
-use anyhow::Result;
+use crate::error::Result;
 
 #[cfg(feature = "cache")]
 use crate::cache::UpdateCache;
@@ -17,6 +17,10 @@ pub enum MemberField {
     Nickname,
     /// Server member avatar.
     Avatar,
+    /// Server member roles.
+    Roles,
+    /// Server member timeout.
+    Timeout,
 }
 
 /// A server member details were updated.
@@ -27,8 +31,9 @@ pub struct ServerMemberUpdateEvent {
     pub member_id: MemberID,
     /// A partial server member.
     pub data: PartialMember,
-    /// A specified field to remove on server member update.
-    pub clear: Option<MemberField>,
+    /// The fields to remove on server member update.
+    #[serde(default)]
+    pub clear: Vec<MemberField>,
 }
 
 impl ServerMemberUpdateEvent {
@@ -58,6 +63,8 @@ pub struct PartialMember {
     /// Member roles.
     #[serde(default)]
     pub roles: Vec<ID>,
+    /// Member timeout end, as an ISO 8601 timestamp.
+    pub timeout: Option<String>,
 }
 
 #[cfg(feature = "cache")]
@@ -65,10 +72,12 @@ pub struct PartialMember {
 impl UpdateCache for ServerMemberUpdateEvent {
     async fn update(&self, ctx: &Context) {
         if let Some(member) = ctx.cache.members.write().await.get_mut(&self.member_id) {
-            if let Some(field) = self.clear {
+            for field in &self.clear {
                 match field {
                     MemberField::Nickname => member.nickname = None,
                     MemberField::Avatar => member.avatar = None,
+                    MemberField::Roles => member.roles.clear(),
+                    MemberField::Timeout => member.timeout = None,
                 }
             }
 
@@ -83,6 +92,10 @@ impl UpdateCache for ServerMemberUpdateEvent {
             if !self.data.roles.is_empty() {
                 member.roles = self.data.roles.clone();
             }
+
+            if let Some(ref timeout) = self.data.timeout {
+                member.timeout = Some(timeout.clone());
+            }
         }
     }
 }
@@ -5,7 +5,13 @@ use crate::{
     Context,
 };
 
-use anyhow::Result;
+use crate::error::Result;
+
+#[cfg(feature = "cache")]
+use crate::{
+    cache::UpdateCache,
+    models::{Channel, TextChannel, VoiceChannel},
+};
 
 /// A server role has been deleted.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -23,3 +29,37 @@ impl ServerRoleDeleteEvent {
         Server::fetch(ctx, &self.server_id).await
     }
 }
+
+#[cfg(feature = "cache")]
+#[async_trait::async_trait]
+impl UpdateCache for ServerRoleDeleteEvent {
+    async fn update(&self, ctx: &Context) {
+        if let Some(server) = ctx.cache.servers.write().await.get_mut(&self.server_id) {
+            server.roles.remove(&self.role_id);
+        }
+
+        for channel in ctx.cache.channels.write().await.values_mut() {
+            match channel {
+                Channel::Text(TextChannel {
+                    server_id,
+                    role_permissions,
+                    ..
+                })
+                | Channel::Voice(VoiceChannel {
+                    server_id,
+                    role_permissions,
+                    ..
+                }) if *server_id == self.server_id => {
+                    role_permissions.remove(&self.role_id);
+                }
+                _ => {}
+            }
+        }
+
+        for member in ctx.cache.members.write().await.values_mut() {
+            if member.id.server_id == self.server_id {
+                member.roles.retain(|id| *id != self.role_id);
+            }
+        }
+    }
+}
@@ -1,14 +1,17 @@
-use {serde::Deserialize, serde_json::Value as Json};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    models::{Server, ID},
+    models::{PartialRole, Server, ID},
     Context,
 };
 
-use anyhow::Result;
+use crate::error::Result;
+
+#[cfg(feature = "cache")]
+use crate::{cache::UpdateCache, models::Role};
 
 /// Specifies a field to remove on server role update.
-#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
 pub enum RoleField {
     /// Role color.
     #[serde(rename = "Colour")]
@@ -23,10 +26,11 @@ pub struct ServerRoleUpdateEvent {
     pub server_id: ID,
     /// Server role id.
     pub role_id: ID,
-    /// A partial server role object.
-    pub data: Json,
-    /// A specified field to remove on server role update.
-    pub clear: Option<RoleField>,
+    /// A partial server role, the whole role if it has just been created.
+    pub data: PartialRole,
+    /// The fields to remove on server role update.
+    #[serde(default)]
+    pub clear: Vec<RoleField>,
 }
 
 impl ServerRoleUpdateEvent {
@@ -35,3 +39,23 @@ impl ServerRoleUpdateEvent {
         Server::fetch(ctx, &self.server_id).await
     }
 }
+
+#[cfg(feature = "cache")]
+#[async_trait::async_trait]
+impl UpdateCache for ServerRoleUpdateEvent {
+    async fn update(&self, ctx: &Context) {
+        if let Some(server) = ctx.cache.servers.write().await.get_mut(&self.server_id) {
+            let role = server
+                .roles
+                .entry(self.role_id.clone())
+                .and_modify(|role| role.apply(&self.data))
+                .or_insert_with(|| Role::from_partial(&self.role_id, &self.data));
+
+            for field in &self.clear {
+                match field {
+                    RoleField::Color => role.colour = None,
+                }
+            }
+        }
+    }
+}
@@ -1,11 +1,11 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    models::{Attachment, Category, Server, SystemMessageChannels, ID},
+    models::{Attachment, Category, Permissions, Server, SystemMessageChannels, ID},
     Context,
 };
 
-use anyhow::Result;
+use crate::error::Result;
 
 #[cfg(feature = "cache")]
 use crate::cache::UpdateCache;
@@ -29,8 +29,9 @@ pub struct ServerUpdateEvent {
     pub server_id: ID,
     /// A partial server object.
     pub data: PartialServer,
-    /// A specified field to remove on server update.
-    pub clear: Option<ServerField>,
+    /// The fields to remove on server update.
+    #[serde(default)]
+    pub clear: Vec<ServerField>,
 }
 
 impl ServerUpdateEvent {
@@ -58,6 +59,8 @@ pub struct PartialServer {
     pub system_messages: Option<SystemMessageChannels>,
     /// Whether server is not safe for work.
     pub nsfw: Option<bool>,
+    /// Permissions given to every member of the server.
+    pub default_permissions: Option<Permissions>,
 }
 
 #[cfg(feature = "cache")]
@@ -65,7 +68,7 @@ pub struct PartialServer {
 impl UpdateCache for ServerUpdateEvent {
     async fn update(&self, ctx: &Context) {
         if let Some(server) = ctx.cache.servers.write().await.get_mut(&self.server_id) {
-            if let Some(field) = self.clear {
+            for field in &self.clear {
                 match field {
                     ServerField::Icon => server.icon = None,
                     ServerField::Banner => server.banner = None,
@@ -100,6 +103,46 @@ impl UpdateCache for ServerUpdateEvent {
             if let Some(nsfw) = self.data.nsfw {
                 server.nsfw = nsfw;
             }
+
+            if let Some(permissions) = self.data.default_permissions {
+                server.default_permissions = permissions;
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json};
+
+    #[test]
+    fn server_update_clears_icon_banner_and_description() {
+        let event: ServerUpdateEvent = serde_json::from_value(json!({
+            "id": "01GZ5ZB8SEDH5TCZ33Q3VJ1K9P",
+            "data": { "name": "Renamed" },
+            "clear": ["Banner", "Icon", "Description"],
+        }))
+        .unwrap();
+
+        assert_eq!(
+            event.clear,
+            [
+                ServerField::Banner,
+                ServerField::Icon,
+                ServerField::Description
+            ]
+        );
+        assert_eq!(event.data.name.as_deref(), Some("Renamed"));
+    }
+
+    #[test]
+    fn server_update_rejects_unknown_fields_to_clear() {
+        let event = serde_json::from_value::<ServerUpdateEvent>(json!({
+            "id": "01GZ5ZB8SEDH5TCZ33Q3VJ1K9P",
+            "data": {},
+            "clear": ["Owner"],
+        }));
+
+        assert!(event.is_err());
+    }
+}
@@ -15,6 +15,9 @@ pub enum ServerEvent {
     Message(Message),
     MessageUpdate(MessageUpdateEvent),
     MessageDelete(MessageDeleteEvent),
+    MessageReact(MessageReactEvent),
+    MessageUnreact(MessageUnreactEvent),
+    MessageRemoveReaction(MessageRemoveReactionEvent),
     ChannelCreate(Channel),
     ChannelUpdate(ChannelUpdateEvent),
     ChannelDelete(ChannelDeleteEvent),
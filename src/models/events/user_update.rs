@@ -5,7 +5,7 @@ use crate::{
     Context,
 };
 
-use anyhow::Result;
+use crate::error::Result;
 
 #[cfg(feature = "cache")]
 use crate::cache::UpdateCache;
@@ -31,8 +31,9 @@ pub struct UserUpdateEvent {
     pub user_id: ID,
     /// A partial user.
     pub data: PartialUser,
-    /// A specified field to remove on user update.
-    pub clear: Option<UserField>,
+    /// The fields to remove on user update.
+    #[serde(default)]
+    pub clear: Vec<UserField>,
 }
 
 impl UserUpdateEvent {
@@ -60,7 +61,7 @@ pub struct PartialUser {
 impl UpdateCache for UserUpdateEvent {
     async fn update(&self, ctx: &Context) {
         if let Some(user) = ctx.cache.users.write().await.get_mut(&self.user_id) {
-            if let Some(field) = self.clear {
+            for field in &self.clear {
                 match field {
                     UserField::StatusText => {
                         if let Some(ref mut status) = user.status {
@@ -86,3 +87,41 @@ impl UpdateCache for UserUpdateEvent {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json};
+
+    #[test]
+    fn user_update_clears_profile_status_and_avatar() {
+        let event: UserUpdateEvent = serde_json::from_value(json!({
+            "id": "01FD58YK5W7QRV5H3D64KTQYX3",
+            "data": { "online": false },
+            "clear": ["ProfileContent", "ProfileBackground", "StatusText", "Avatar"],
+        }))
+        .unwrap();
+
+        assert_eq!(
+            event.clear,
+            [
+                UserField::ProfileContent,
+                UserField::ProfileBackground,
+                UserField::StatusText,
+                UserField::Avatar
+            ]
+        );
+        assert_eq!(event.data.online, Some(false));
+    }
+
+    #[test]
+    fn user_update_without_clear_keeps_every_field() {
+        let event: UserUpdateEvent = serde_json::from_value(json!({
+            "id": "01FD58YK5W7QRV5H3D64KTQYX3",
+            "data": { "status": { "text": "Away", "presence": "Busy" } },
+        }))
+        .unwrap();
+
+        assert!(event.clear.is_empty());
+        assert!(event.data.status.is_some());
+    }
+}
@@ -0,0 +1,183 @@
+use {
+    futures_util::{stream, Stream, TryStreamExt},
+    serde::{Deserialize, Serialize},
+};
+
+use crate::{
+    error::Result,
+    models::{Member, Message, User, ID},
+    Context,
+};
+
+/// The maximum number of messages returned by the API in a single request.
+const PAGE_SIZE: usize = 100;
+
+/// Sort order of messages.
+#[derive(Debug, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MessageSort {
+    /// Most relevant messages first, only supported by searches.
+    Relevance,
+    /// Newest messages first.
+    #[default]
+    Latest,
+    /// Oldest messages first.
+    Oldest,
+}
+
+/// A page of messages, with the users and members bundled with them if requested.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct MessageBatch {
+    /// The messages.
+    pub messages: Vec<Message>,
+    /// The authors and mentioned users of the messages.
+    #[serde(default)]
+    pub users: Vec<User>,
+    /// The members of the authors, if the channel belongs to a server.
+    #[serde(default)]
+    pub members: Vec<Member>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum MessagesResponse {
+    Messages(Vec<Message>),
+    Batch(MessageBatch),
+}
+
+impl From<MessagesResponse> for MessageBatch {
+    fn from(response: MessagesResponse) -> Self {
+        match response {
+            MessagesResponse::Messages(messages) => Self {
+                messages,
+                ..Default::default()
+            },
+            MessagesResponse::Batch(batch) => batch,
+        }
+    }
+}
+
+/// Query builder for the message history of a channel.
+///
+/// The messages are fetched page by page while the stream is polled.
+#[derive(Debug, Clone, Serialize)]
+pub struct MessageHistory {
+    #[serde(skip)]
+    channel_id: ID,
+    #[serde(skip)]
+    total: Option<usize>,
+    limit: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    before: Option<ID>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    after: Option<ID>,
+    sort: MessageSort,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nearby: Option<ID>,
+    include_users: bool,
+}
+
+impl MessageHistory {
+    /// Creates a new query for the messages of the specified channel, newest first.
+    pub fn new(channel_id: &ID) -> Self {
+        Self {
+            channel_id: channel_id.clone(),
+            total: None,
+            limit: PAGE_SIZE,
+            before: None,
+            after: None,
+            sort: MessageSort::Latest,
+            nearby: None,
+            include_users: false,
+        }
+    }
+
+    /// Set the maximum number of messages to fetch, all of them if not set.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.total = Some(limit);
+        self
+    }
+
+    /// Fetch only the messages sent before the specified message.
+    pub fn before(mut self, id: &ID) -> Self {
+        self.before = Some(id.clone());
+        self
+    }
+
+    /// Fetch only the messages sent after the specified message.
+    pub fn after(mut self, id: &ID) -> Self {
+        self.after = Some(id.clone());
+        self
+    }
+
+    /// Set the sort order, [`MessageSort::Latest`] by default.
+    pub fn sort(mut self, sort: MessageSort) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Fetch the messages around the specified message, in a single page.
+    ///
+    /// Overrides [`before`](Self::before), [`after`](Self::after) and [`sort`](Self::sort).
+    pub fn nearby(mut self, id: &ID) -> Self {
+        self.nearby = Some(id.clone());
+        self
+    }
+
+    /// Set whether the users and members are bundled with the messages.
+    pub fn include_users(mut self, include_users: bool) -> Self {
+        self.include_users = include_users;
+        self
+    }
+
+    /// Returns a stream of the pages of messages.
+    pub fn pages(self, ctx: &Context) -> impl Stream<Item = Result<MessageBatch>> {
+        stream::try_unfold((ctx.clone(), Some(self)), |(ctx, query)| async move {
+            let Some(mut query) = query else {
+                return Ok(None);
+            };
+
+            query.limit = query.total.map_or(PAGE_SIZE, |total| total.min(PAGE_SIZE));
+            if query.limit == 0 {
+                return Ok(None);
+            }
+
+            let path = format!("channels/{}/messages", query.channel_id);
+            let batch: MessageBatch = ctx
+                .http_client
+                .get_with_query::<MessagesResponse, _>(path, &query)
+                .await?
+                .into();
+
+            let next = query.next_page(&batch);
+            Ok(Some((batch, (ctx, next))))
+        })
+    }
+
+    /// Returns a stream of the messages.
+    pub fn stream(self, ctx: &Context) -> impl Stream<Item = Result<Message>> {
+        self.pages(ctx)
+            .map_ok(|batch| stream::iter(batch.messages.into_iter().map(Ok)))
+            .try_flatten()
+    }
+
+    /// Returns the query of the page after the specified one, if any.
+    fn next_page(mut self, batch: &MessageBatch) -> Option<Self> {
+        let last = batch.messages.last()?;
+
+        if batch.messages.len() < self.limit || self.nearby.is_some() {
+            return None;
+        }
+
+        if let Some(ref mut total) = self.total {
+            *total = total.saturating_sub(batch.messages.len());
+        }
+
+        match self.sort {
+            MessageSort::Latest => self.before = Some(last.id.clone()),
+            MessageSort::Oldest => self.after = Some(last.id.clone()),
+            MessageSort::Relevance => return None,
+        }
+
+        Some(self)
+    }
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+/// Interactions allowed on a message.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct Interactions {
+    /// Reactions added to the message when it is sent.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reactions: Vec<String>,
+    /// Whether users can only react with the [`reactions`](Self::reactions).
+    #[serde(default)]
+    pub restrict_reactions: bool,
+}
+
+impl Interactions {
+    /// Creates new [Interactions] with the specified reactions.
+    pub fn new(reactions: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            reactions: reactions.into_iter().map(Into::into).collect(),
+            restrict_reactions: false,
+        }
+    }
+
+    /// Set whether users can only react with the [`reactions`](Self::reactions).
+    pub fn restrict_reactions(mut self, restrict: bool) -> Self {
+        self.restrict_reactions = restrict;
+        self
+    }
+}
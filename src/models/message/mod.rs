@@ -1,21 +1,24 @@
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-pub use {content::*, edited::*, embed::*, masquerade::*};
+pub use {content::*, edited::*, embed::*, history::*, interactions::*, masquerade::*, purge::*};
 
 mod content;
 mod edited;
 mod embed;
+mod history;
+mod interactions;
 mod masquerade;
+mod purge;
 
-use serde::Deserialize;
+use {ahash::AHashMap, percent_encoding::utf8_percent_encode, serde::Deserialize};
 
 use crate::{
-    builders::{CreateMessage, EditMessage},
-    models::{Attachment, ID},
+    builders::{CreateMessage, EditMessage, MessageSearch},
+    models::{id_timestamp, Attachment, ID, PATH_SEGMENT},
     Context,
 };
 
-use anyhow::Result;
+use crate::error::Result;
 
 #[cfg(feature = "cache")]
 use crate::{cache::UpdateCache, models::Channel};
@@ -52,11 +55,24 @@ pub struct Message {
     pub masquerade: Option<Masquerade>,
     /// Edition date.
     pub edited: Option<MessageEdited>,
+    /// Message reactions, the ids of the users who reacted by emoji.
+    #[serde(default)]
+    pub reactions: AHashMap<String, Vec<ID>>,
+    /// Interactions allowed on the message.
+    pub interactions: Option<Interactions>,
 }
 
 impl Message {
-    /// Get a [`Message`] from the API.
+    /// Get a [`Message`] from the cache or the API.
+    ///
+    /// With the `cache` feature, the cached message is returned without checking the API,
+    /// like the other `fetch` methods. The cache is kept up to date by the message events.
     pub async fn fetch(ctx: &Context, channel_id: &ID, id: &ID) -> Result<Self> {
+        #[cfg(feature = "cache")]
+        if let Some(msg) = ctx.cache.message(id).await {
+            return Ok(msg);
+        }
+
         let path = format!("channels/{}/messages/{}", channel_id, id);
         let msg = ctx.http_client.get(&path).await?;
 
@@ -64,13 +80,38 @@ impl Message {
     }
 
     /// Creates a new [`Message`] in the specified channel ID from a [`CreateMessage`] builder
-    pub async fn create(ctx: &Context, channel_id: &ID, builder: CreateMessage) -> Result<Self> {
+    ///
+    /// The files of the builder are uploaded before the message is sent.
+    pub async fn create(
+        ctx: &Context,
+        channel_id: &ID,
+        mut builder: CreateMessage,
+    ) -> Result<Self> {
+        builder.upload_files(&ctx.http_client).await?;
+
         let path = format!("channels/{}/messages", channel_id);
         let msg = ctx.http_client.post(&path, builder).await?;
 
         Ok(msg)
     }
 
+    /// Returns the time the [`Message`] was sent at, taken from its id.
+    pub fn created_at(&self) -> SystemTime {
+        id_timestamp(&self.id).unwrap_or(UNIX_EPOCH)
+    }
+
+    /// Search the messages of the specified channel ID with a [`MessageSearch`] builder.
+    pub async fn search(
+        ctx: &Context,
+        channel_id: &ID,
+        builder: MessageSearch,
+    ) -> Result<MessageBatch> {
+        let path = format!("channels/{}/search", channel_id);
+        let response: MessagesResponse = ctx.http_client.post(&path, builder).await?;
+
+        Ok(response.into())
+    }
+
     /// Whether the [`Message`] has been edited or not.
     pub fn is_edited(&self) -> bool {
         self.edited.is_some()
@@ -124,6 +165,65 @@ impl Message {
         Ok(())
     }
 
+    /// React to the [`Message`] with an emoji id, or the emoji itself for unicode emojis.
+    pub async fn react(&self, ctx: &Context, emoji: &str) -> Result<()> {
+        ctx.http_client.put_empty(self.reaction_path(emoji)).await
+    }
+
+    /// Remove the reaction of the current user to the [`Message`].
+    pub async fn unreact(&self, ctx: &Context, emoji: &str) -> Result<()> {
+        ctx.http_client.delete(self.reaction_path(emoji)).await
+    }
+
+    /// Remove the reaction of the specified user to the [`Message`].
+    pub async fn remove_reaction(&self, ctx: &Context, emoji: &str, user_id: &ID) -> Result<()> {
+        ctx.http_client
+            .delete_with_query(self.reaction_path(emoji), &[("user_id", user_id)])
+            .await
+    }
+
+    /// Remove all the reactions with an emoji from the [`Message`].
+    pub async fn remove_all_reactions(&self, ctx: &Context, emoji: &str) -> Result<()> {
+        ctx.http_client
+            .delete_with_query(self.reaction_path(emoji), &[("remove_all", true)])
+            .await
+    }
+
+    /// Remove all the reactions from the [`Message`].
+    pub async fn clear_reactions(&self, ctx: &Context) -> Result<()> {
+        let path = format!(
+            "channels/{}/messages/{}/reactions",
+            self.channel_id, self.id
+        );
+        ctx.http_client.delete(path).await
+    }
+
+    /// Returns the path of the reactions with an emoji, percent-encoded for unicode emojis.
+    fn reaction_path(&self, emoji: &str) -> String {
+        format!(
+            "channels/{}/messages/{}/reactions/{}",
+            self.channel_id,
+            self.id,
+            utf8_percent_encode(emoji, PATH_SEGMENT)
+        )
+    }
+
+    /// Delete the specified messages of a channel in bulk.
+    ///
+    /// The messages must have been sent in the past week. They are deleted by chunks of `100`,
+    /// the maximum allowed by the API.
+    pub async fn delete_many(ctx: &Context, channel_id: &ID, ids: &[ID]) -> Result<()> {
+        let path = format!("channels/{}/messages/bulk", channel_id);
+
+        for ids in ids.chunks(BULK_DELETE_MAX) {
+            ctx.http_client
+                .delete_with_body(&path, BulkDelete { ids })
+                .await?;
+        }
+
+        Ok(())
+    }
+
     /// Delete the [`Message`] after the specified delay is over.
     pub async fn delete_after(&self, ctx: &Context, delay: Duration) -> Result<()> {
         tokio::time::sleep(delay).await;
@@ -139,6 +239,8 @@ impl Message {
 #[async_trait::async_trait]
 impl UpdateCache for Message {
     async fn update(&self, ctx: &Context) {
+        ctx.cache.messages.write().await.insert(self.clone());
+
         if let Some(channel) = ctx.cache.channels.write().await.get_mut(&self.channel_id) {
             match channel {
                 Channel::Text(channel) => channel.last_message_id = Some(self.id.clone()),
@@ -149,3 +251,33 @@ impl UpdateCache for Message {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reaction_path_is_percent_encoded() {
+        let msg: Message = serde_json::from_value(serde_json::json!({
+            "_id": "01HZ8N7K3M5XTQW9RFYB2C4D6E",
+            "channel": "01HZ8N7K3M5XTQW9RFYB2C4D6F",
+            "author": "01HZ8N7K3M5XTQW9RFYB2C4D6G",
+            "content": "hello",
+        }))
+        .unwrap();
+        let prefix = "channels/01HZ8N7K3M5XTQW9RFYB2C4D6F/messages/01HZ8N7K3M5XTQW9RFYB2C4D6E";
+
+        assert_eq!(
+            msg.reaction_path("01HZ8N7K3M5XTQW9RFYB2C4D6H"),
+            format!("{prefix}/reactions/01HZ8N7K3M5XTQW9RFYB2C4D6H")
+        );
+        assert_eq!(
+            msg.reaction_path("👍"),
+            format!("{prefix}/reactions/%F0%9F%91%8D")
+        );
+        assert_eq!(
+            msg.reaction_path("a/b?c#d"),
+            format!("{prefix}/reactions/a%2Fb%3Fc%23d")
+        );
+    }
+}
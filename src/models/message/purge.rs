@@ -0,0 +1,137 @@
+use {
+    futures_util::{pin_mut, TryStreamExt},
+    serde::Serialize,
+    std::time::{Duration, SystemTime},
+};
+
+use crate::{
+    error::Result,
+    models::{Message, MessageHistory, ID},
+    Context,
+};
+
+/// The maximum number of messages deleted by the API in a single request.
+pub(crate) const BULK_DELETE_MAX: usize = 100;
+/// The maximum age of the messages that can be deleted in bulk.
+const BULK_DELETE_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+type MessageFilter = Box<dyn Fn(&Message) -> bool + Send + Sync>;
+
+#[derive(Debug, Serialize)]
+pub(crate) struct BulkDelete<'a> {
+    pub(crate) ids: &'a [ID],
+}
+
+/// Helper to delete the recent messages of a channel in bulk.
+///
+/// The messages are scanned from the newest to the oldest, and only the messages sent in the
+/// past week can be deleted, as older messages cannot be deleted in bulk.
+pub struct Purge {
+    channel_id: ID,
+    limit: Option<usize>,
+    before: Option<ID>,
+    author_id: Option<ID>,
+    max_age: Duration,
+    filter: Option<MessageFilter>,
+}
+
+impl Purge {
+    /// Creates a new purge of the messages of the specified channel.
+    pub fn new(channel_id: &ID) -> Self {
+        Self {
+            channel_id: channel_id.clone(),
+            limit: None,
+            before: None,
+            author_id: None,
+            max_age: BULK_DELETE_MAX_AGE,
+            filter: None,
+        }
+    }
+
+    /// Set the maximum number of messages to delete.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Delete only the messages sent before the specified message.
+    pub fn before(mut self, id: &ID) -> Self {
+        self.before = Some(id.clone());
+        self
+    }
+
+    /// Delete only the messages sent by the specified user.
+    pub fn author(mut self, id: &ID) -> Self {
+        self.author_id = Some(id.clone());
+        self
+    }
+
+    /// Delete only the messages younger than the specified age, capped to a week.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age.min(BULK_DELETE_MAX_AGE);
+        self
+    }
+
+    /// Delete only the messages matching the predicate, for example on their content.
+    pub fn filter(mut self, filter: impl Fn(&Message) -> bool + Send + Sync + 'static) -> Self {
+        self.filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Delete the messages and return how many were deleted.
+    pub async fn execute(self, ctx: &Context) -> Result<usize> {
+        let limit = self.limit.unwrap_or(usize::MAX);
+        let oldest = SystemTime::now() - self.max_age;
+
+        let mut history = MessageHistory::new(&self.channel_id);
+        if let Some(ref before) = self.before {
+            history = history.before(before);
+        }
+
+        let messages = history.stream(ctx);
+        pin_mut!(messages);
+
+        let mut ids = Vec::with_capacity(BULK_DELETE_MAX);
+        let mut deleted = 0;
+
+        while deleted + ids.len() < limit {
+            let Some(message) = messages.try_next().await? else {
+                break;
+            };
+
+            if message.created_at() < oldest {
+                break;
+            }
+
+            if self.matches(&message) {
+                ids.push(message.id);
+            }
+
+            if ids.len() == BULK_DELETE_MAX {
+                Message::delete_many(ctx, &self.channel_id, &ids).await?;
+                deleted += ids.len();
+                ids.clear();
+            }
+        }
+
+        if !ids.is_empty() {
+            Message::delete_many(ctx, &self.channel_id, &ids).await?;
+            deleted += ids.len();
+        }
+
+        Ok(deleted)
+    }
+
+    fn matches(&self, message: &Message) -> bool {
+        if let Some(ref author_id) = self.author_id {
+            if *author_id != message.author_id {
+                return false;
+            }
+        }
+
+        match self.filter {
+            Some(ref filter) => filter(message),
+            None => true,
+        }
+    }
+}
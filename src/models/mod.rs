@@ -1,14 +1,230 @@
 //! Revolt API models.
 
+use {
+    percent_encoding::{AsciiSet, NON_ALPHANUMERIC},
+    std::time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
 #[doc(inline)]
-pub use {attachment::*, channel::*, message::*, server::*, user::*};
+pub use {attachment::*, channel::*, message::*, node_info::*, server::*, user::*};
 
 mod attachment;
 mod channel;
 pub mod events;
 mod message;
+mod node_info;
 mod server;
 mod user;
 
 /// Models id type.
 pub type ID = String;
+
+/// The characters escaped in a path segment, all but the unreserved ones.
+pub(crate) const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Returns the creation time encoded in the first 10 characters of an id (ULID).
+pub(crate) fn id_timestamp(id: &ID) -> Option<SystemTime> {
+    const ALPHABET: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+    let millis = id.get(..10)?.bytes().try_fold(0u64, |millis, byte| {
+        let digit = ALPHABET
+            .iter()
+            .position(|c| *c == byte.to_ascii_uppercase())?;
+        Some(millis << 5 | digit as u64)
+    })?;
+
+    Some(UNIX_EPOCH + Duration::from_millis(millis))
+}
+
+/// Parses an ISO 8601 timestamp as sent by the API, like `2024-01-31T12:30:00.000Z`.
+pub(crate) fn parse_timestamp(timestamp: &str) -> Option<SystemTime> {
+    fn number(s: &str) -> Option<i64> {
+        s.bytes()
+            .all(|byte| byte.is_ascii_digit())
+            .then(|| s.parse().ok())?
+    }
+
+    let (date, time) = timestamp.split_once(['T', ' '])?;
+    let (time, offset) = match time.strip_suffix(['Z', 'z']) {
+        Some(time) => (time, 0),
+        None => {
+            let index = time.rfind(['+', '-'])?;
+            let (time, offset) = time.split_at(index);
+            let (hours, minutes) = offset[1..].split_once(':')?;
+            let (hours, minutes) = (number(hours)?, number(minutes)?);
+
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+
+            let seconds = hours * 3600 + minutes * 60;
+            (
+                time,
+                if offset.starts_with('-') {
+                    -seconds
+                } else {
+                    seconds
+                },
+            )
+        }
+    };
+
+    let mut date = date.splitn(3, '-').map(number);
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+
+    let (time, fraction) = time.split_once('.').unwrap_or((time, ""));
+    let mut time = time.splitn(3, ':').map(number);
+    let (hours, minutes, seconds) = (time.next()??, time.next()??, time.next()??);
+    let nanos = number(fraction).map_or(0, |_| {
+        format!("{:0<9}", &fraction[..fraction.len().min(9)])
+            .parse()
+            .unwrap_or(0)
+    });
+
+    let leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let month_days = match month {
+        2 if leap_year => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return None,
+    };
+
+    // A second of 60 is a leap second.
+    if !(1..=month_days).contains(&day) || hours > 23 || minutes > 59 || seconds > 60 {
+        return None;
+    }
+
+    // Days since the epoch of a date of the proleptic Gregorian calendar.
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146097 + day_of_era - 719468;
+
+    let secs = days * 86400 + hours * 3600 + minutes * 60 + seconds - offset;
+
+    UNIX_EPOCH.checked_add(Duration::new(secs.try_into().ok()?, nanos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(millis: u64) -> Option<SystemTime> {
+        Some(UNIX_EPOCH + Duration::from_millis(millis))
+    }
+
+    #[test]
+    fn id_timestamp_decodes_ulids() {
+        // Example of the ULID specification.
+        assert_eq!(
+            id_timestamp(&"01ARYZ6S41TSV4RRFFQ69G5FAV".into()),
+            millis(1469918176385)
+        );
+        assert_eq!(
+            id_timestamp(&"01aryz6s41tsv4rrffq69g5fav".into()),
+            millis(1469918176385)
+        );
+        assert_eq!(id_timestamp(&"0000000000".into()), millis(0));
+        assert_eq!(
+            id_timestamp(&"7ZZZZZZZZZZZZZZZZZZZZZZZZZ".into()),
+            millis(281474976710655)
+        );
+    }
+
+    #[test]
+    fn id_timestamp_rejects_invalid_ids() {
+        assert_eq!(id_timestamp(&"01ARYZ6S4".into()), None);
+        assert_eq!(id_timestamp(&"01ARYZ6SIL".into()), None);
+        assert_eq!(id_timestamp(&"01ARYZ6S4é".into()), None);
+    }
+
+    #[test]
+    fn parse_timestamp_parses_utc() {
+        assert_eq!(
+            parse_timestamp("2024-01-31T12:30:00Z"),
+            millis(1706704200000)
+        );
+        assert_eq!(
+            parse_timestamp("2000-02-29T00:00:00.000Z"),
+            millis(951782400000)
+        );
+        assert_eq!(parse_timestamp("1970-01-01T00:00:00Z"), millis(0));
+    }
+
+    #[test]
+    fn parse_timestamp_parses_fractional_seconds() {
+        assert_eq!(
+            parse_timestamp("2024-01-31T12:30:00.5Z"),
+            millis(1706704200500)
+        );
+        assert_eq!(
+            parse_timestamp("2024-01-31T12:30:00.123456789Z"),
+            Some(UNIX_EPOCH + Duration::new(1706704200, 123456789))
+        );
+        assert_eq!(
+            parse_timestamp("2024-01-31T12:30:00.1234567891Z"),
+            Some(UNIX_EPOCH + Duration::new(1706704200, 123456789))
+        );
+    }
+
+    #[test]
+    fn parse_timestamp_applies_offsets() {
+        assert_eq!(
+            parse_timestamp("2024-01-31T14:30:00+02:00"),
+            millis(1706704200000)
+        );
+        assert_eq!(
+            parse_timestamp("2024-01-31T07:00:00.250-05:30"),
+            millis(1706704200250)
+        );
+    }
+
+    #[test]
+    fn parse_timestamp_rejects_invalid_timestamps() {
+        assert_eq!(parse_timestamp(""), None);
+        assert_eq!(parse_timestamp("2024-01-31"), None);
+        assert_eq!(parse_timestamp("2024-01-31T12:30Z"), None);
+        assert_eq!(parse_timestamp("2024-01-31T12:30:00"), None);
+        assert_eq!(parse_timestamp("2024-01-xxT12:30:00Z"), None);
+        assert_eq!(parse_timestamp("1969-12-31T23:59:59Z"), None);
+    }
+
+    #[test]
+    fn parse_timestamp_rejects_out_of_range_fields() {
+        assert_eq!(parse_timestamp("2024-00-31T12:30:00Z"), None);
+        assert_eq!(parse_timestamp("2024-13-01T12:30:00Z"), None);
+        assert_eq!(parse_timestamp("2024-01-00T12:30:00Z"), None);
+        assert_eq!(parse_timestamp("2024-01-32T12:30:00Z"), None);
+        assert_eq!(parse_timestamp("2024-04-31T12:30:00Z"), None);
+        assert_eq!(parse_timestamp("2023-02-29T12:30:00Z"), None);
+        assert_eq!(parse_timestamp("1900-02-29T12:30:00Z"), None);
+        assert_eq!(parse_timestamp("2024-01-31T24:00:00Z"), None);
+        assert_eq!(parse_timestamp("2024-01-31T12:60:00Z"), None);
+        assert_eq!(parse_timestamp("2024-01-31T12:30:61Z"), None);
+        assert_eq!(parse_timestamp("2024-01-31T12:30:00+24:00"), None);
+        assert_eq!(parse_timestamp("2024-01-31T12:30:00+01:60"), None);
+    }
+
+    #[test]
+    fn parse_timestamp_accepts_leap_days_and_seconds() {
+        assert_eq!(
+            parse_timestamp("2024-02-29T00:00:00Z"),
+            millis(1709164800000)
+        );
+        assert_eq!(
+            parse_timestamp("2000-02-29T00:00:00Z"),
+            millis(951782400000)
+        );
+        assert_eq!(
+            parse_timestamp("2016-12-31T23:59:60Z"),
+            millis(1483228800000)
+        );
+    }
+}
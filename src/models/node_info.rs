@@ -0,0 +1,39 @@
+use serde::Deserialize;
+
+/// Information about a Revolt instance, returned by the root of the REST API.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct NodeInfo {
+    /// Revolt API version.
+    pub revolt: String,
+    /// Features enabled on the instance.
+    pub features: NodeFeatures,
+    /// The url of the WebSocket API.
+    pub ws: String,
+    /// The url of the web app, if the instance has one.
+    #[serde(default)]
+    pub app: Option<String>,
+}
+
+/// Features enabled on a Revolt instance.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct NodeFeatures {
+    /// Whether the instance requires an invite to register.
+    #[serde(default)]
+    pub invite_only: bool,
+    /// The file server.
+    pub autumn: NodeFeature,
+    /// The link embedding server, disabled if the instance does not have one.
+    #[serde(default)]
+    pub january: NodeFeature,
+}
+
+/// A service of a Revolt instance.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct NodeFeature {
+    /// Whether the service is enabled.
+    #[serde(default)]
+    pub enabled: bool,
+    /// The url of the service.
+    #[serde(default)]
+    pub url: String,
+}
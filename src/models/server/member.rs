@@ -1,12 +1,18 @@
-use serde::{Deserialize, Serialize};
+use {
+    serde::{Deserialize, Serialize},
+    std::{cmp::Reverse, time::SystemTime},
+};
 
 use crate::{
     builders::EditMember,
-    models::{Attachment, ID},
+    models::{
+        parse_timestamp, Attachment, Channel, Permissions, Role, Server, TextChannel, VoiceChannel,
+        ID,
+    },
     Context,
 };
 
-use anyhow::Result;
+use crate::error::Result;
 
 /// A server member id.
 #[derive(Debug, Clone, PartialEq, Deserialize, Hash, Eq)]
@@ -41,6 +47,8 @@ pub struct Member {
     /// Member roles ids.
     #[serde(default)]
     pub roles: Vec<ID>,
+    /// Member timeout end, as an ISO 8601 timestamp.
+    pub timeout: Option<String>,
 }
 
 impl Member {
@@ -56,6 +64,103 @@ impl Member {
             .await
     }
 
+    /// Returns whether the member is in timeout.
+    pub fn is_timed_out(&self) -> bool {
+        self.timeout
+            .as_deref()
+            .and_then(parse_timestamp)
+            .is_some_and(|end| end > SystemTime::now())
+    }
+
+    /// Calculate the permissions of the member in the server, before any channel override.
+    pub fn server_permissions(&self, server: &Server) -> Permissions {
+        if self.id.user_id == server.owner_id {
+            return Permissions::all();
+        }
+
+        let permissions = self
+            .roles_by_rank(server)
+            .fold(server.default_permissions, |permissions, role| {
+                permissions.apply(role.permissions)
+            });
+
+        self.restrict_in_timeout(permissions)
+    }
+
+    /// Calculate the permissions of the member in a channel of the server.
+    ///
+    /// Returns no permissions if the channel does not belong to the server.
+    pub fn permissions_in(&self, channel: &Channel, server: &Server) -> Permissions {
+        let (default_permissions, role_permissions) = match channel {
+            Channel::Text(TextChannel {
+                server_id,
+                default_permissions,
+                role_permissions,
+                ..
+            })
+            | Channel::Voice(VoiceChannel {
+                server_id,
+                default_permissions,
+                role_permissions,
+                ..
+            }) if *server_id == server.id => (default_permissions, role_permissions),
+            _ => return Permissions::empty(),
+        };
+
+        if self.id.user_id == server.owner_id {
+            return Permissions::all();
+        }
+
+        let mut permissions = self.server_permissions(server);
+
+        if let Some(default_permissions) = default_permissions {
+            permissions = permissions.apply(*default_permissions);
+        }
+
+        for role in self.roles_by_rank(server) {
+            if let Some(role_permissions) = role_permissions.get(&role.id) {
+                permissions = permissions.apply(*role_permissions);
+            }
+        }
+
+        let permissions = self.restrict_in_timeout(permissions);
+
+        if permissions.contains(Permissions::VIEW_CHANNEL) {
+            permissions
+        } else {
+            Permissions::empty()
+        }
+    }
+
+    /// Get the channel and its server from the cache or API and calculate the permissions of
+    /// the member in the channel.
+    pub async fn permissions(&self, ctx: &Context, channel_id: &ID) -> Result<Permissions> {
+        let channel = Channel::fetch(ctx, channel_id).await?;
+        let server = Server::fetch(ctx, &self.id.server_id).await?;
+
+        Ok(self.permissions_in(&channel, &server))
+    }
+
+    /// Returns the roles of the member from the lowest to the highest priority.
+    fn roles_by_rank<'a>(&self, server: &'a Server) -> impl Iterator<Item = &'a Role> {
+        let mut roles: Vec<_> = self
+            .roles
+            .iter()
+            .filter_map(|id| server.roles.get(id))
+            .collect();
+        roles.sort_by_key(|role| Reverse(role.rank));
+
+        roles.into_iter()
+    }
+
+    fn restrict_in_timeout(&self, permissions: Permissions) -> Permissions {
+        if self.is_timed_out() {
+            permissions & Permissions::ALLOW_IN_TIMEOUT
+        } else {
+            permissions
+        }
+    }
+
     /// Edit the member.
     pub async fn edit(&self, ctx: &Context, builder: EditMember) -> Result<()> {
         ctx.http_client
@@ -103,3 +208,190 @@ impl CreateBan {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::models::Override, ahash::AHashMap, serde_json::json};
+
+    const SERVER: &str = "01HZ8N7K3M5XTQW9RFYB2C4D60";
+    const OWNER: &str = "01HZ8N7K3M5XTQW9RFYB2C4D61";
+    const USER: &str = "01HZ8N7K3M5XTQW9RFYB2C4D62";
+    const LOW: &str = "01HZ8N7K3M5XTQW9RFYB2C4D63";
+    const HIGH: &str = "01HZ8N7K3M5XTQW9RFYB2C4D64";
+
+    const VIEW: Permissions = Permissions::VIEW_CHANNEL;
+    const HISTORY: Permissions = Permissions::READ_MESSAGE_HISTORY;
+    const SEND: Permissions = Permissions::SEND_MESSAGE;
+    const REACT: Permissions = Permissions::REACT;
+    const MANAGE: Permissions = Permissions::MANAGE_MESSAGES;
+
+    /// A server where `LOW` allows managing messages but denies reacting, and `HIGH` has
+    /// priority and allows reacting.
+    fn server() -> Server {
+        serde_json::from_value(json!({
+            "_id": SERVER,
+            "owner": OWNER,
+            "name": "Server",
+            "channels": [],
+            "default_permissions": (VIEW | HISTORY | SEND | REACT).bits(),
+            "roles": {
+                LOW: { "name": "Low", "rank": 10, "permissions": Override::new(MANAGE, REACT) },
+                HIGH: { "name": "High", "rank": 1, "permissions": Override::allowing(REACT) },
+            },
+        }))
+        .unwrap()
+    }
+
+    fn member(user_id: &str, roles: &[&str], timeout: Option<&str>) -> Member {
+        serde_json::from_value(json!({
+            "_id": { "server": SERVER, "user": user_id },
+            "roles": roles,
+            "timeout": timeout,
+        }))
+        .unwrap()
+    }
+
+    fn channel(server_id: &str, default: Option<Override>, roles: &[(&str, Override)]) -> Channel {
+        let roles: AHashMap<_, _> = roles.iter().cloned().collect();
+
+        serde_json::from_value(json!({
+            "channel_type": "TextChannel",
+            "_id": "01HZ8N7K3M5XTQW9RFYB2C4D65",
+            "server": server_id,
+            "name": "general",
+            "default_permissions": default,
+            "role_permissions": roles,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn server_permissions_apply_roles_by_rank() {
+        let server = server();
+        let cases = [
+            (member(USER, &[], None), VIEW | HISTORY | SEND | REACT),
+            (member(USER, &[LOW], None), VIEW | HISTORY | SEND | MANAGE),
+            (member(USER, &[HIGH], None), VIEW | HISTORY | SEND | REACT),
+            (
+                member(USER, &[LOW, HIGH], None),
+                VIEW | HISTORY | SEND | REACT | MANAGE,
+            ),
+            (
+                member(USER, &[HIGH, LOW], None),
+                VIEW | HISTORY | SEND | REACT | MANAGE,
+            ),
+            (
+                member(USER, &["01HZ8N7K3M5XTQW9RFYB2C4D6Z"], None),
+                VIEW | HISTORY | SEND | REACT,
+            ),
+            (member(OWNER, &[], None), Permissions::all()),
+        ];
+
+        for (member, expected) in cases {
+            assert_eq!(
+                member.server_permissions(&server),
+                expected,
+                "{:?}",
+                member.roles
+            );
+        }
+    }
+
+    #[test]
+    fn channel_permissions_apply_default_then_roles_by_rank() {
+        let server = server();
+        let channel = channel(
+            SERVER,
+            Some(Override::denying(SEND)),
+            &[
+                (LOW, Override::allowing(SEND)),
+                (HIGH, Override::new(Permissions::empty(), SEND | MANAGE)),
+            ],
+        );
+
+        let cases = [
+            (member(USER, &[], None), VIEW | HISTORY | REACT),
+            (member(USER, &[LOW], None), VIEW | HISTORY | SEND | MANAGE),
+            (member(USER, &[HIGH], None), VIEW | HISTORY | REACT),
+            (member(USER, &[LOW, HIGH], None), VIEW | HISTORY | REACT),
+            (member(USER, &[HIGH, LOW], None), VIEW | HISTORY | REACT),
+            (member(OWNER, &[], None), Permissions::all()),
+        ];
+
+        for (member, expected) in cases {
+            assert_eq!(
+                member.permissions_in(&channel, &server),
+                expected,
+                "{:?}",
+                member.roles
+            );
+        }
+    }
+
+    #[test]
+    fn channel_permissions_require_viewing_the_channel() {
+        let server = server();
+        let hidden = channel(
+            SERVER,
+            Some(Override::denying(VIEW)),
+            &[(HIGH, Override::allowing(VIEW))],
+        );
+
+        let cases = [
+            (member(USER, &[], None), Permissions::empty()),
+            (member(USER, &[LOW], None), Permissions::empty()),
+            (member(USER, &[HIGH], None), VIEW | HISTORY | SEND | REACT),
+            (member(OWNER, &[], None), Permissions::all()),
+        ];
+
+        for (member, expected) in cases {
+            assert_eq!(
+                member.permissions_in(&hidden, &server),
+                expected,
+                "{:?}",
+                member.roles
+            );
+        }
+    }
+
+    #[test]
+    fn channel_permissions_of_other_servers_are_empty() {
+        let server = server();
+        let other = channel("01HZ8N7K3M5XTQW9RFYB2C4D66", None, &[]);
+
+        assert_eq!(
+            member(USER, &[], None).permissions_in(&other, &server),
+            Permissions::empty()
+        );
+        assert_eq!(
+            member(OWNER, &[], None).permissions_in(&other, &server),
+            Permissions::empty()
+        );
+    }
+
+    #[test]
+    fn timeout_keeps_only_viewing_permissions() {
+        let server = server();
+        let channel = channel(SERVER, None, &[]);
+
+        let cases = [
+            (Some("2999-01-01T00:00:00Z"), VIEW | HISTORY),
+            (Some("2000-01-01T00:00:00Z"), VIEW | HISTORY | SEND | REACT),
+            (Some("not a timestamp"), VIEW | HISTORY | SEND | REACT),
+            (None, VIEW | HISTORY | SEND | REACT),
+        ];
+
+        for (timeout, expected) in cases {
+            let member = member(USER, &[], timeout);
+            assert_eq!(member.server_permissions(&server), expected, "{timeout:?}");
+            assert_eq!(
+                member.permissions_in(&channel, &server),
+                expected,
+                "{timeout:?}"
+            );
+        }
+
+        let owner = member(OWNER, &[], Some("2999-01-01T00:00:00Z"));
+        assert_eq!(owner.server_permissions(&server), Permissions::all());
+    }
+}
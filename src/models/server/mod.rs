@@ -1,20 +1,21 @@
-pub use {category::*, flags::*, member::*, permissions::*, system_message_channels::*};
+pub use {category::*, flags::*, member::*, permissions::*, role::*, system_message_channels::*};
 
 mod category;
 mod flags;
 mod member;
 mod permissions;
+mod role;
 mod system_message_channels;
 
-use serde::Deserialize;
+use {ahash::AHashMap, serde::Deserialize};
 
 use crate::{
-    builders::{CreateChannel, EditServer},
+    builders::{CreateChannel, CreateRole, EditRole, EditServer, SetPermissions},
     models::{Attachment, Channel, User, ID},
     Context,
 };
 
-use anyhow::Result;
+use crate::error::Result;
 
 /// A server.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -47,6 +48,12 @@ pub struct Server {
     /// Server is not safe for work.
     #[serde(default)]
     pub nsfw: bool,
+    /// Server roles, by id.
+    #[serde(default, deserialize_with = "deserialize_roles")]
+    pub roles: AHashMap<ID, Role>,
+    /// Permissions given to every member of the server.
+    #[serde(default)]
+    pub default_permissions: Permissions,
 }
 
 impl Server {
@@ -89,6 +96,62 @@ impl Server {
         ctx.http_client.delete(format!("servers/{}", self.id)).await
     }
 
+    /// Create a role in the server.
+    pub async fn create_role(&self, ctx: &Context, builder: CreateRole) -> Result<Role> {
+        let created: CreatedRole = ctx
+            .http_client
+            .post(format!("servers/{}/roles", self.id), builder)
+            .await?;
+
+        Ok(Role {
+            id: created.id,
+            ..created.role
+        })
+    }
+
+    /// Edit a role of the server.
+    pub async fn edit_role(&self, ctx: &Context, role_id: &ID, builder: EditRole) -> Result<()> {
+        ctx.http_client
+            .patch(format!("servers/{}/roles/{}", self.id, role_id), builder)
+            .await
+    }
+
+    /// Delete a role of the server.
+    pub async fn delete_role(&self, ctx: &Context, role_id: &ID) -> Result<()> {
+        ctx.http_client
+            .delete(format!("servers/{}/roles/{}", self.id, role_id))
+            .await
+    }
+
+    /// Set the permissions allowed and denied by a role of the server.
+    pub async fn set_role_permissions(
+        &self,
+        ctx: &Context,
+        role_id: &ID,
+        permissions: Override,
+    ) -> Result<()> {
+        ctx.http_client
+            .put(
+                format!("servers/{}/permissions/{}", self.id, role_id),
+                SetPermissions::allow_deny(permissions),
+            )
+            .await
+    }
+
+    /// Set the permissions given to every member of the server.
+    pub async fn set_default_permissions(
+        &self,
+        ctx: &Context,
+        permissions: Permissions,
+    ) -> Result<()> {
+        ctx.http_client
+            .put(
+                format!("servers/{}/permissions/default", self.id),
+                SetPermissions::value(permissions),
+            )
+            .await
+    }
+
     /// Unban a user from the server.
     pub async fn unban(&self, ctx: &Context, user_id: &ID) -> Result<()> {
         ctx.http_client
@@ -97,6 +160,12 @@ impl Server {
     }
 }
 
+#[derive(Debug, Deserialize)]
+struct CreatedRole {
+    id: ID,
+    role: Role,
+}
+
 #[derive(Debug, Deserialize)]
 struct ServerMembers {
     members: Vec<Member>,
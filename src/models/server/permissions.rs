@@ -1,11 +1,22 @@
-use bitflags::bitflags;
-use serde::Deserialize;
+use {
+    bitflags::bitflags,
+    serde::{
+        de::{Error as DeError, Visitor},
+        Deserialize, Deserializer, Serialize, Serializer,
+    },
+    std::{
+        fmt::{self, Display, Formatter},
+        str::FromStr,
+    },
+    thiserror::Error,
+};
 
 bitflags! {
-    /// Enum of revolt permissions
-    #[derive(Deserialize, Debug)]
-    #[serde(transparent)]
-    pub struct RevoltPermission: u64 {
+    /// Revolt permissions.
+    ///
+    /// Taken from [here](https://developers.revolt.chat/stack/delta/permissions).
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+    pub struct Permissions: u64 {
         /// Manage the channel or channels on the server
         const MANAGE_CHANNEL = 1;
         /// Manage the server
@@ -31,7 +42,7 @@ bitflags! {
         /// Change own avatar
         const CHANGE_AVATAR = 4096;
         ///	Remove other's avatars below their ranking
-        const REMOVE_AVATAR = 8192;
+        const REMOVE_AVATARS = 8192;
         ///	View a channel
         const VIEW_CHANNEL = 1048576;
         ///	Read a channel's past message history
@@ -66,3 +77,248 @@ bitflags! {
         const MOVE_MEMBERS = 34359738368;
     }
 }
+
+impl Permissions {
+    /// Permissions kept by the members in timeout.
+    pub const ALLOW_IN_TIMEOUT: Self = Self::VIEW_CHANNEL.union(Self::READ_MESSAGE_HISTORY);
+
+    /// Returns the permissions with an override applied on top of them.
+    pub fn apply(self, permissions: Override) -> Self {
+        (self | permissions.allow) - permissions.deny
+    }
+
+    /// Get a permission from its name, either as the constant (`SEND_MESSAGE`) or as named by
+    /// the API (`SendMessage`).
+    pub fn parse_name(name: &str) -> Option<Self> {
+        let name = name.bytes().filter(|byte| *byte != b'_');
+
+        Self::all().iter_names().find_map(|(flag, permission)| {
+            let flag = flag.bytes().filter(|byte| *byte != b'_');
+
+            flag.eq(name.clone().map(|byte| byte.to_ascii_uppercase()))
+                .then_some(permission)
+        })
+    }
+}
+
+/// Permissions are displayed as their names separated by `|`, like `VIEW_CHANNEL | SEND_MESSAGE`.
+impl Display for Permissions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        bitflags::parser::to_writer(self, f)
+    }
+}
+
+/// Error returned when parsing [`Permissions`] from a string fails.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Unknown permission: {0}")]
+pub struct ParsePermissionsError(String);
+
+/// Permissions are parsed from names or bits separated by `|`, like `VIEW_CHANNEL | SendMessage`.
+impl FromStr for Permissions {
+    type Err = ParsePermissionsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split('|')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .try_fold(Self::empty(), |permissions, name| {
+                let permission = match name.strip_prefix("0x") {
+                    Some(hex) => u64::from_str_radix(hex, 16)
+                        .ok()
+                        .map(Self::from_bits_retain),
+                    None => name
+                        .parse()
+                        .ok()
+                        .map(Self::from_bits_retain)
+                        .or_else(|| Self::parse_name(name)),
+                };
+
+                permission
+                    .map(|permission| permissions | permission)
+                    .ok_or_else(|| ParsePermissionsError(name.to_owned()))
+            })
+    }
+}
+
+// The API represents permissions as their bits, except in errors where a single permission is
+// named.
+impl Serialize for Permissions {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.bits().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Permissions {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct PermissionsVisitor;
+
+        impl Visitor<'_> for PermissionsVisitor {
+            type Value = Permissions;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                f.write_str("permission bits or names")
+            }
+
+            fn visit_u64<E: DeError>(self, bits: u64) -> Result<Self::Value, E> {
+                Ok(Permissions::from_bits_retain(bits))
+            }
+
+            fn visit_i64<E: DeError>(self, bits: i64) -> Result<Self::Value, E> {
+                u64::try_from(bits)
+                    .map(Permissions::from_bits_retain)
+                    .map_err(E::custom)
+            }
+
+            fn visit_str<E: DeError>(self, s: &str) -> Result<Self::Value, E> {
+                s.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(PermissionsVisitor)
+    }
+}
+
+/// Permissions allowed and denied on top of the inherited ones.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Override {
+    /// Allowed permissions.
+    #[serde(rename(deserialize = "a"), alias = "allow")]
+    pub allow: Permissions,
+    /// Denied permissions.
+    #[serde(rename(deserialize = "d"), alias = "deny")]
+    pub deny: Permissions,
+}
+
+impl Override {
+    /// Creates a new [`Override`].
+    pub fn new(allow: Permissions, deny: Permissions) -> Self {
+        Self { allow, deny }
+    }
+
+    /// Creates an [`Override`] allowing the specified permissions.
+    pub fn allowing(permissions: Permissions) -> Self {
+        Self::new(permissions, Permissions::empty())
+    }
+
+    /// Creates an [`Override`] denying the specified permissions.
+    pub fn denying(permissions: Permissions) -> Self {
+        Self::new(Permissions::empty(), permissions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VIEW: Permissions = Permissions::VIEW_CHANNEL;
+    const SEND: Permissions = Permissions::SEND_MESSAGE;
+    const REACT: Permissions = Permissions::REACT;
+
+    #[test]
+    fn parse_name_accepts_constant_and_api_names() {
+        let cases = [
+            ("SEND_MESSAGE", Some(SEND)),
+            ("SendMessage", Some(SEND)),
+            ("sendmessage", Some(SEND)),
+            ("send_message", Some(SEND)),
+            ("VIEW_CHANNEL", Some(VIEW)),
+            ("ViewChannel", Some(VIEW)),
+            ("RemoveAvatars", Some(Permissions::REMOVE_AVATARS)),
+            ("SendMessages", None),
+            ("Send Message", None),
+            ("", None),
+        ];
+
+        for (name, expected) in cases {
+            assert_eq!(Permissions::parse_name(name), expected, "{name:?}");
+        }
+    }
+
+    #[test]
+    fn from_str_parses_names_and_bits() {
+        let cases = [
+            ("", Ok(Permissions::empty())),
+            ("SendMessage", Ok(SEND)),
+            ("VIEW_CHANNEL | SendMessage", Ok(VIEW | SEND)),
+            ("VIEW_CHANNEL|SEND_MESSAGE|", Ok(VIEW | SEND)),
+            ("1048576", Ok(VIEW)),
+            ("0x100000 | React", Ok(VIEW | REACT)),
+            ("0x100000000000", Ok(Permissions::from_bits_retain(1 << 44))),
+            ("SendMessage | Administrator", Err("Administrator")),
+            ("0xZZ", Err("0xZZ")),
+            ("-1", Err("-1")),
+        ];
+
+        for (s, expected) in cases {
+            let expected = expected.map_err(|name| ParsePermissionsError(name.to_owned()));
+            assert_eq!(s.parse::<Permissions>(), expected, "{s:?}");
+        }
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for permissions in [
+            Permissions::empty(),
+            SEND,
+            VIEW | SEND | REACT,
+            Permissions::all(),
+        ] {
+            assert_eq!(permissions.to_string().parse(), Ok(permissions));
+        }
+
+        assert_eq!((VIEW | SEND).to_string(), "VIEW_CHANNEL | SEND_MESSAGE");
+    }
+
+    #[test]
+    fn deserializes_bits_and_names() {
+        let cases = [
+            ("1048576", VIEW),
+            ("5242880", VIEW | SEND),
+            (r#""SendMessage""#, SEND),
+            (r#""ViewChannel | SendMessage""#, VIEW | SEND),
+        ];
+
+        for (json, expected) in cases {
+            assert_eq!(
+                serde_json::from_str::<Permissions>(json).unwrap(),
+                expected,
+                "{json}"
+            );
+        }
+
+        assert!(serde_json::from_str::<Permissions>("-1").is_err());
+        assert!(serde_json::from_str::<Permissions>(r#""Unknown""#).is_err());
+        assert_eq!(serde_json::to_string(&(VIEW | SEND)).unwrap(), "5242880");
+    }
+
+    #[test]
+    fn apply_allows_then_denies() {
+        let cases = [
+            (VIEW, Override::default(), VIEW),
+            (VIEW, Override::allowing(SEND), VIEW | SEND),
+            (VIEW | SEND, Override::denying(SEND), VIEW),
+            (VIEW, Override::new(SEND, SEND), VIEW),
+            (VIEW, Override::new(SEND | REACT, VIEW), SEND | REACT),
+        ];
+
+        for (permissions, apply, expected) in cases {
+            assert_eq!(
+                permissions.apply(apply),
+                expected,
+                "{permissions} {apply:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn override_deserializes_short_and_long_names() {
+        let expected = Override::new(SEND, REACT);
+
+        for json in [
+            r#"{"a":4194304,"d":536870912}"#,
+            r#"{"allow":4194304,"deny":536870912}"#,
+        ] {
+            assert_eq!(serde_json::from_str::<Override>(json).unwrap(), expected);
+        }
+    }
+}
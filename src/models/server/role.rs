@@ -0,0 +1,93 @@
+use {
+    ahash::AHashMap,
+    serde::{Deserialize, Deserializer},
+};
+
+use crate::models::{Override, ID};
+
+/// A server role.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Role {
+    /// Role id.
+    #[serde(skip)]
+    pub id: ID,
+    /// Role name.
+    pub name: String,
+    /// Permissions allowed and denied by the role.
+    pub permissions: Override,
+    /// Role colour, any valid CSS colour.
+    pub colour: Option<String>,
+    /// Whether the role is displayed separately in the member list.
+    #[serde(default)]
+    pub hoist: bool,
+    /// Role rank, a lower rank has priority over the higher ones.
+    #[serde(default)]
+    pub rank: i64,
+}
+
+/// A partial server role.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct PartialRole {
+    /// Role name.
+    pub name: Option<String>,
+    /// Permissions allowed and denied by the role.
+    pub permissions: Option<Override>,
+    /// Role colour.
+    pub colour: Option<String>,
+    /// Whether the role is displayed separately in the member list.
+    pub hoist: Option<bool>,
+    /// Role rank.
+    pub rank: Option<i64>,
+}
+
+#[cfg(feature = "cache")]
+impl Role {
+    pub(crate) fn from_partial(id: &ID, partial: &PartialRole) -> Self {
+        let mut role = Self {
+            id: id.clone(),
+            name: String::new(),
+            permissions: Override::default(),
+            colour: None,
+            hoist: false,
+            rank: 0,
+        };
+        role.apply(partial);
+
+        role
+    }
+
+    pub(crate) fn apply(&mut self, partial: &PartialRole) {
+        if let Some(ref name) = partial.name {
+            self.name = name.clone();
+        }
+
+        if let Some(permissions) = partial.permissions {
+            self.permissions = permissions;
+        }
+
+        if let Some(ref colour) = partial.colour {
+            self.colour = Some(colour.clone());
+        }
+
+        if let Some(hoist) = partial.hoist {
+            self.hoist = hoist;
+        }
+
+        if let Some(rank) = partial.rank {
+            self.rank = rank;
+        }
+    }
+}
+
+/// Deserializes the roles of a server, filling their ids from the keys of the map.
+pub(crate) fn deserialize_roles<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<AHashMap<ID, Role>, D::Error> {
+    let mut roles = AHashMap::<ID, Role>::deserialize(deserializer)?;
+
+    for (id, role) in roles.iter_mut() {
+        role.id = id.clone();
+    }
+
+    Ok(roles)
+}
@@ -13,7 +13,7 @@ use crate::{
     Context,
 };
 
-use anyhow::Result;
+use crate::error::Result;
 
 /// A user.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
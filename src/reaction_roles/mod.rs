@@ -0,0 +1,254 @@
+//! Roles given to the users who react to a message.
+//!
+//! Each [`ReactionRole`] maps a message and an emoji to a role: the role is added to the users
+//! who react with the emoji and removed from the users who remove their reaction.
+//!
+//! Reactions received while the client was disconnected are caught up after each
+//! (re)connection, by reading the reactions of every message again. Roles are only added
+//! at that time, as a missing reaction does not tell whether the role was given by other means.
+//!
+//! The [`Client`](crate::Client) handles the reactions one at a time, in the order it receives them.
+//! The roles of a member are also updated one at a time, from the roles returned by the API,
+//! so that close reactions of the same member do not overwrite each other.
+
+use {
+    ahash::AHashMap,
+    serde::{Deserialize, Serialize},
+    std::sync::{Arc, Mutex as StdMutex},
+    tokio::sync::{Mutex, RwLock},
+    tracing::warn,
+};
+
+use crate::{
+    builders::EditMember,
+    error::Result,
+    models::{
+        events::{MemberField, MessageReactEvent, MessageUnreactEvent, ServerEvent},
+        Member, Message, ID,
+    },
+    Context,
+};
+
+pub use store::*;
+
+mod store;
+
+type MemberLocks = StdMutex<AHashMap<(ID, ID), Arc<Mutex<()>>>>;
+
+/// A role given to the users who react to a message with an emoji.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ReactionRole {
+    /// Server id.
+    pub server_id: ID,
+    /// Message channel id.
+    pub channel_id: ID,
+    /// Message id.
+    pub message_id: ID,
+    /// Emoji id, or the emoji itself for unicode emojis.
+    pub emoji: String,
+    /// Role id.
+    pub role_id: ID,
+}
+
+impl ReactionRole {
+    /// Creates a new [`ReactionRole`].
+    pub fn new(
+        server_id: &ID,
+        channel_id: &ID,
+        message_id: &ID,
+        emoji: impl Into<String>,
+        role_id: &ID,
+    ) -> Self {
+        Self {
+            server_id: server_id.clone(),
+            channel_id: channel_id.clone(),
+            message_id: message_id.clone(),
+            emoji: emoji.into(),
+            role_id: role_id.clone(),
+        }
+    }
+
+    pub(crate) fn is_for(&self, message_id: &ID, emoji: &str) -> bool {
+        self.message_id == *message_id && self.emoji == emoji
+    }
+}
+
+/// The reaction roles handled by the [`Client`](crate::Client).
+///
+/// Register it with [`ClientBuilder::reaction_roles`](crate::ClientBuilder::reaction_roles).
+#[derive(Clone)]
+pub struct ReactionRoles {
+    store: Arc<dyn ReactionRoleStore>,
+    roles: Arc<RwLock<AHashMap<(ID, String), ReactionRole>>>,
+    /// Held while updating the roles of a member, by server and user id.
+    members: Arc<MemberLocks>,
+}
+
+impl ReactionRoles {
+    /// Creates new [`ReactionRoles`] and load the saved ones from the store.
+    pub async fn load(store: impl ReactionRoleStore) -> Result<Self> {
+        let roles = store
+            .load()
+            .await?
+            .into_iter()
+            .map(|role| ((role.message_id.clone(), role.emoji.clone()), role))
+            .collect();
+
+        Ok(Self {
+            store: Arc::new(store),
+            roles: Arc::new(RwLock::new(roles)),
+            members: Default::default(),
+        })
+    }
+
+    /// Add a reaction role, replacing the one with the same message and emoji.
+    pub async fn add(&self, role: ReactionRole) -> Result<()> {
+        self.store.save(&role).await?;
+        self.roles
+            .write()
+            .await
+            .insert((role.message_id.clone(), role.emoji.clone()), role);
+
+        Ok(())
+    }
+
+    /// Remove the reaction role of a message and emoji.
+    pub async fn remove(&self, message_id: &ID, emoji: &str) -> Result<Option<ReactionRole>> {
+        self.store.delete(message_id, emoji).await?;
+
+        Ok(self
+            .roles
+            .write()
+            .await
+            .remove(&(message_id.clone(), emoji.to_owned())))
+    }
+
+    /// Get the reaction role of a message and emoji.
+    pub async fn get(&self, message_id: &ID, emoji: &str) -> Option<ReactionRole> {
+        self.roles
+            .read()
+            .await
+            .get(&(message_id.clone(), emoji.to_owned()))
+            .cloned()
+    }
+
+    /// Get all the reaction roles.
+    pub async fn roles(&self) -> Vec<ReactionRole> {
+        self.roles.read().await.values().cloned().collect()
+    }
+
+    pub(crate) async fn handle(&self, ctx: &Context, event: &ServerEvent) -> Result<()> {
+        match event {
+            ServerEvent::Ready(_) => self.reconcile(ctx).await,
+            ServerEvent::MessageReact(event) => self.on_react(ctx, event).await,
+            ServerEvent::MessageUnreact(event) => self.on_unreact(ctx, event).await,
+            _ => Ok(()),
+        }
+    }
+
+    /// Add the role to the user who reacted, if the reaction is a reaction role.
+    pub async fn on_react(&self, ctx: &Context, event: &MessageReactEvent) -> Result<()> {
+        match self.get(&event.message_id, &event.emoji_id).await {
+            Some(role) if event.user_id != ctx.http_client.bot().id => {
+                self.set_role(ctx, &role, &event.user_id, true).await
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Remove the role from the user who removed their reaction, if the reaction is a reaction role.
+    pub async fn on_unreact(&self, ctx: &Context, event: &MessageUnreactEvent) -> Result<()> {
+        match self.get(&event.message_id, &event.emoji_id).await {
+            Some(role) if event.user_id != ctx.http_client.bot().id => {
+                self.set_role(ctx, &role, &event.user_id, false).await
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Read the reactions of every message again and add the roles of the missed reactions.
+    ///
+    /// Called by the [`Client`](crate::Client) after each (re)connection.
+    pub async fn reconcile(&self, ctx: &Context) -> Result<()> {
+        for role in self.roles().await {
+            let path = format!("channels/{}/messages/{}", role.channel_id, role.message_id);
+            let message: Message = match ctx.http_client.get(path).await {
+                Ok(message) => message,
+                Err(err) => {
+                    warn!(target: "ReactionRoles", "Err fetching {}: {}", role.message_id, err);
+                    continue;
+                }
+            };
+
+            let bot_id = &ctx.http_client.bot().id;
+            let users = message.reactions.get(&role.emoji).into_iter().flatten();
+
+            for user_id in users.filter(|user_id| *user_id != bot_id) {
+                if let Err(err) = self.set_role(ctx, &role, user_id, true).await {
+                    warn!(target: "ReactionRoles", "Err adding role to {}: {}", user_id, err);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Add or remove the role of a reaction role to a member, after the other updates of
+    /// the member are done.
+    async fn set_role(
+        &self,
+        ctx: &Context,
+        role: &ReactionRole,
+        user_id: &ID,
+        add: bool,
+    ) -> Result<()> {
+        let key = (role.server_id.clone(), user_id.clone());
+        let lock = self
+            .members
+            .lock()
+            .unwrap()
+            .entry(key.clone())
+            .or_default()
+            .clone();
+
+        let result = {
+            let _guard = lock.lock().await;
+            set_role(ctx, role, user_id, add).await
+        };
+
+        // Forget the lock once no other update of the member is waiting for it.
+        let mut members = self.members.lock().unwrap();
+        if Arc::strong_count(&lock) == 2 {
+            members.remove(&key);
+        }
+
+        result
+    }
+}
+
+/// Add or remove the role of a reaction role to a member.
+///
+/// The member is fetched from the API, as the cached roles may not include the last updates.
+async fn set_role(ctx: &Context, role: &ReactionRole, user_id: &ID, add: bool) -> Result<()> {
+    let path = format!("servers/{}/members/{}", role.server_id, user_id);
+    let member: Member = ctx.http_client.get(path).await?;
+
+    if member.roles.contains(&role.role_id) == add {
+        return Ok(());
+    }
+
+    let roles = member
+        .roles
+        .iter()
+        .filter(|id| **id != role.role_id)
+        .chain(add.then_some(&role.role_id));
+
+    let builder = roles.fold(EditMember::new(), |builder, id| builder.role(id));
+    let builder = if member.roles.len() == 1 && !add {
+        builder.remove(MemberField::Roles)
+    } else {
+        builder
+    };
+
+    member.edit(ctx, builder).await
+}
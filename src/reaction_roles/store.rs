@@ -0,0 +1,180 @@
+use {
+    async_trait::async_trait,
+    std::{io::ErrorKind, path::PathBuf},
+    tokio::{fs::File, io::AsyncWriteExt, sync::Mutex},
+};
+
+use super::ReactionRole;
+use crate::{error::Result, models::ID};
+
+/// Storage of the [`ReactionRole`]s, so that they persist across restarts.
+#[async_trait]
+pub trait ReactionRoleStore: Send + Sync + 'static {
+    /// Load all the reaction roles.
+    async fn load(&self) -> Result<Vec<ReactionRole>>;
+
+    /// Save a reaction role, replacing the one with the same message and emoji.
+    async fn save(&self, role: &ReactionRole) -> Result<()>;
+
+    /// Delete the reaction role of a message and emoji.
+    async fn delete(&self, message_id: &ID, emoji: &str) -> Result<()>;
+}
+
+/// A store that keeps nothing, the reaction roles are lost on restart.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MemoryStore;
+
+#[async_trait]
+impl ReactionRoleStore for MemoryStore {
+    async fn load(&self) -> Result<Vec<ReactionRole>> {
+        Ok(Vec::new())
+    }
+
+    async fn save(&self, _role: &ReactionRole) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete(&self, _message_id: &ID, _emoji: &str) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A store that keeps the reaction roles in a JSON file.
+///
+/// The file is replaced atomically on each change, so that a crash while saving does not
+/// lose the reaction roles saved before.
+#[derive(Debug)]
+pub struct JsonFileStore {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl JsonFileStore {
+    /// Creates a new store, the file is created on the first save.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    async fn read(&self) -> Result<Vec<ReactionRole>> {
+        match tokio::fs::read(&self.path).await {
+            Ok(content) => Ok(serde_json::from_slice(&content)?),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn write(&self, roles: &[ReactionRole]) -> Result<()> {
+        let content = serde_json::to_vec_pretty(roles)?;
+
+        // Write a temporary file next to the store and move it over the store once complete.
+        let mut temp_path = self.path.clone().into_os_string();
+        temp_path.push(".tmp");
+
+        let mut file = File::create(&temp_path).await?;
+        file.write_all(&content).await?;
+        file.sync_all().await?;
+        drop(file);
+
+        tokio::fs::rename(&temp_path, &self.path).await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ReactionRoleStore for JsonFileStore {
+    async fn load(&self) -> Result<Vec<ReactionRole>> {
+        let _lock = self.lock.lock().await;
+
+        self.read().await
+    }
+
+    async fn save(&self, role: &ReactionRole) -> Result<()> {
+        let _lock = self.lock.lock().await;
+
+        let mut roles = self.read().await?;
+        roles.retain(|saved| !saved.is_for(&role.message_id, &role.emoji));
+        roles.push(role.clone());
+
+        self.write(&roles).await
+    }
+
+    async fn delete(&self, message_id: &ID, emoji: &str) -> Result<()> {
+        let _lock = self.lock.lock().await;
+
+        let mut roles = self.read().await?;
+        roles.retain(|saved| !saved.is_for(message_id, emoji));
+
+        self.write(&roles).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::error::RSError};
+
+    fn path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("resvolt-{}-{}.json", name, std::process::id()))
+    }
+
+    fn role(message_id: &str, emoji: &str) -> ReactionRole {
+        ReactionRole::new(
+            &"01J0C4HNL7W4J6QJ3PE9AB1ZK0".into(),
+            &"01J0C4HNL7W4J6QJ3PE9AB1ZK1".into(),
+            &message_id.into(),
+            emoji,
+            &"01J0C4HNL7W4J6QJ3PE9AB1ZK2".into(),
+        )
+    }
+
+    #[tokio::test]
+    async fn json_file_store_saves_and_deletes() {
+        let path = path("store");
+        let store = JsonFileStore::new(&path);
+
+        assert!(store.load().await.unwrap().is_empty());
+
+        store
+            .save(&role("01J0C4HNL7W4J6QJ3PE9AB1ZK3", "👍"))
+            .await
+            .unwrap();
+        store
+            .save(&role("01J0C4HNL7W4J6QJ3PE9AB1ZK3", "👎"))
+            .await
+            .unwrap();
+        store
+            .save(&role("01J0C4HNL7W4J6QJ3PE9AB1ZK3", "👍"))
+            .await
+            .unwrap();
+        assert_eq!(store.load().await.unwrap().len(), 2);
+
+        store
+            .delete(&"01J0C4HNL7W4J6QJ3PE9AB1ZK3".into(), "👍")
+            .await
+            .unwrap();
+        assert_eq!(
+            store.load().await.unwrap(),
+            [role("01J0C4HNL7W4J6QJ3PE9AB1ZK3", "👎")]
+        );
+
+        let mut temp_path = path.clone().into_os_string();
+        temp_path.push(".tmp");
+        assert!(!PathBuf::from(temp_path).exists());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn json_file_store_reports_invalid_json() {
+        let path = path("invalid");
+        std::fs::write(&path, "[{").unwrap();
+
+        let result = JsonFileStore::new(&path).load().await;
+        assert!(matches!(result, Err(RSError::Json(_))), "{result:?}");
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
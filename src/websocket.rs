@@ -11,82 +11,176 @@ use crate::{
     models::events::{ClientEvent, ServerEvent},
 };
 
-use anyhow::Result;
+use crate::error::Result;
+
+/// Encoding of the messages exchanged with the Revolt WebSocket API.
+///
+/// Defaults to [`WireFormat::MessagePack`] when the `msgpack` feature is enabled,
+/// and to [`WireFormat::Json`] otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WireFormat {
+    /// JSON text messages.
+    Json,
+    /// MessagePack binary messages.
+    MessagePack,
+}
+
+impl WireFormat {
+    fn query(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::MessagePack => "msgpack",
+        }
+    }
+}
 
-#[cfg(not(feature = "msgpack"))]
-const BONFIRE_API: &str = "wss://ws.revolt.chat";
-#[cfg(feature = "msgpack")]
-const BONFIRE_API: &str = "wss://ws.revolt.chat/?format=msgpack";
+impl Default for WireFormat {
+    fn default() -> Self {
+        #[cfg(feature = "msgpack")]
+        return Self::MessagePack;
+        #[cfg(not(feature = "msgpack"))]
+        return Self::Json;
+    }
+}
+
+/// State of the connection to the Revolt WebSocket API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// The connection is open, but the client is not authenticated yet.
+    Connected,
+    /// The client is authenticated and receives events.
+    Authenticated,
+    /// The connection was lost.
+    Disconnected {
+        /// Why the connection was lost.
+        reason: String,
+    },
+    /// The client is trying to open a new connection.
+    Reconnecting {
+        /// The current attempt, starting from `1`.
+        attempt: u32,
+    },
+    /// The connection was closed by the client.
+    Closed,
+}
 
 #[derive(Debug)]
 pub struct WebSocketClient {
+    url: String,
+    format: WireFormat,
     stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
     heartbeat_dur: Duration,
+    heartbeat_timeout: Duration,
     last_heartbeat: (Instant, Instant),
+    state: ConnectionState,
 }
 
 impl WebSocketClient {
-    pub async fn connect() -> Result<Self> {
-        let (stream, _) = connect_async(BONFIRE_API).await?;
+    pub async fn connect(url: &str, format: WireFormat) -> Result<Self> {
+        let url = format!("{}/?format={}", url.trim_end_matches('/'), format.query());
+
+        let (stream, _) = connect_async(&url).await?;
         let now = Instant::now();
 
         Ok(Self {
+            url,
+            format,
             stream,
             heartbeat_dur: Duration::from_secs(10),
+            heartbeat_timeout: Duration::from_secs(30),
             last_heartbeat: (now, now),
+            state: ConnectionState::Connected,
         })
     }
 
-    pub async fn send(&mut self, event: ClientEvent) -> Result<()> {
-        #[cfg(not(feature = "msgpack"))]
-        let msg = Message::Text(serde_json::to_string(&event).unwrap());
-        #[cfg(feature = "msgpack")]
-        let msg = Message::Binary(rmp_serde::to_vec(&event).unwrap());
+    /// Marks the start of a new reconnection attempt.
+    pub fn begin_reconnect(&mut self, attempt: u32) {
+        self.state = ConnectionState::Reconnecting { attempt };
+    }
+
+    /// Replaces the underlying stream with a new connection.
+    pub async fn reconnect(&mut self) -> Result<()> {
+        let (stream, _) = connect_async(&self.url).await?;
+        let now = Instant::now();
 
-        self.stream.send(msg).await?;
+        self.stream = stream;
+        self.last_heartbeat = (now, now);
+        self.state = ConnectionState::Connected;
 
         Ok(())
     }
 
+    pub fn state(&self) -> &ConnectionState {
+        &self.state
+    }
+
+    /// Marks the connection as lost, unless it is being closed by the client.
+    fn disconnected(&mut self, reason: impl Into<String>) {
+        if self.state != ConnectionState::Closed {
+            self.state = ConnectionState::Disconnected {
+                reason: reason.into(),
+            };
+        }
+    }
+
+    pub async fn send(&mut self, event: ClientEvent) -> Result<()> {
+        let msg = match self.format {
+            WireFormat::Json => Message::Text(serde_json::to_string(&event).map_err(|err| {
+                RSError::Unknown(format!("Cannot serialize a websocket message: {}", err))
+            })?),
+            WireFormat::MessagePack => {
+                Message::Binary(rmp_serde::to_vec_named(&event).map_err(|err| {
+                    RSError::Unknown(format!("Cannot serialize a websocket message: {}", err))
+                })?)
+            }
+        };
+
+        if let Err(err) = self.stream.send(msg).await {
+            self.disconnected(err.to_string());
+            return Err(RSError::Ws(Box::new(err)));
+        }
+
+        Ok(())
+    }
+
+    /// Waits for the next event, returns `None` if the connection was closed.
+    ///
+    /// Text and binary messages are decoded as JSON and MessagePack respectively,
+    /// whatever the configured [`WireFormat`] is.
     pub async fn accept(&mut self) -> Option<Result<ServerEvent>> {
-        match self.stream.next().await? {
-            Ok(msg) => match msg {
-                #[cfg(not(feature = "msgpack"))]
-                Message::Text(text) => {
-                    let event = serde_json::from_str(&text).map_err(|_| {
-                        RSError::Unknown(format!(
-                            "Cannot deserialize a websocket message: {:?}",
-                            text
-                        ))
-                    });
-
-                    match event {
-                        Ok(event) => {
-                            self.check_pong(&event);
-
-                            Some(Ok(event))
-                        }
-                        Err(err) => Some(Err(err.into())),
-                    }
+        loop {
+            let Some(msg) = self.stream.next().await else {
+                self.disconnected("The server closed the connection");
+                return None;
+            };
+
+            let event = match msg {
+                Ok(Message::Text(text)) => serde_json::from_str(&text).map_err(|_| {
+                    RSError::Unknown(format!(
+                        "Cannot deserialize a websocket message: {:?}",
+                        text
+                    ))
+                }),
+                Ok(Message::Binary(buf)) => rmp_serde::from_slice(&buf).map_err(|_| {
+                    RSError::Unknown("Cannot deserialize a binary websocket message".into())
+                }),
+                Ok(Message::Close(_)) => {
+                    self.disconnected("The server closed the connection");
+                    return None;
                 }
-                #[cfg(feature = "msgpack")]
-                Message::Binary(buf) => {
-                    let event = rmp_serde::from_slice(&buf).map_err(|_| {
-                        RSError::Unknown("Cannot deserialize a binary websocket message".into())
-                    });
-
-                    match event {
-                        Ok(event) => {
-                            self.check_pong(&event);
-                            Some(Ok(event))
-                        }
-                        Err(err) => Some(Err(err.into())),
-                    }
+                // Control frames are answered by the stream itself.
+                Ok(Message::Ping(_) | Message::Pong(_) | Message::Frame(_)) => continue,
+                Err(err) => {
+                    self.disconnected(err.to_string());
+                    return Some(Err(RSError::Ws(Box::new(err))));
                 }
-                Message::Close(_) => None,
-                _ => unreachable!(),
-            },
-            Err(err) => Some(Err(err.into())),
+            };
+
+            if let Ok(ref event) = event {
+                self.update_state(event);
+            }
+
+            return Some(event);
         }
     }
 
@@ -100,13 +194,28 @@ impl WebSocketClient {
 
     pub async fn close(&mut self) -> Result<()> {
         info!(target: "WebSocketClient", "Closing the connection");
+        self.state = ConnectionState::Closed;
         self.stream.close(None).await?;
 
         Ok(())
     }
 
+    pub fn heartbeat_dur(&self) -> Duration {
+        self.heartbeat_dur
+    }
+
+    /// Sends a heartbeat if needed, fails if the server did not answer the last one in time.
     pub async fn check_heartbeat(&mut self) -> Result<()> {
-        let dur = Instant::now() - self.last_heartbeat.0;
+        let (ping, pong) = self.last_heartbeat;
+
+        if ping > pong && ping.elapsed() >= self.heartbeat_timeout {
+            let reason = format!("No heartbeat received for {:?}", ping.elapsed());
+            self.disconnected(reason.clone());
+
+            return Err(RSError::ConnectionLost(reason));
+        }
+
+        let dur = Instant::now() - ping;
 
         if dur >= self.heartbeat_dur {
             self.heartbeat().await?;
@@ -123,9 +232,11 @@ impl WebSocketClient {
         Ok(())
     }
 
-    fn check_pong(&mut self, event: &ServerEvent) {
-        if let ServerEvent::Pong = event {
-            self.last_heartbeat.1 = Instant::now();
+    fn update_state(&mut self, event: &ServerEvent) {
+        match event {
+            ServerEvent::Pong => self.last_heartbeat.1 = Instant::now(),
+            ServerEvent::Authenticated => self.state = ConnectionState::Authenticated,
+            _ => {}
         }
     }
 }